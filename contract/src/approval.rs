@@ -1,6 +1,7 @@
 use crate::*;
 
 use near_sdk::{assert_one_yocto, env, ext_contract, require, AccountId, Balance, Gas, Promise};
+use near_sdk::json_types::U128;
 
 
 /// Trait used when it's desired to have a non-fungible token that has a
@@ -42,6 +43,20 @@ pub trait NonFungibleTokenApproval {
         msg: Option<String>,
     ) -> Option<Promise>;
 
+    /// Add an approved account for each of a list of tokens (at most `MAX_TOKENS_PER_APPROVE`), all of which must be owned by the caller.
+    ///
+    /// Storage for all new approvals is charged once (excess deposit refunded once). If `msg` is
+    /// present, `nft_on_approve` is called on `account_id` for each token, splitting the gas left
+    /// after approving between the calls (panics if less than `MIN_GAS_FOR_NFT_ON_APPROVE` each).
+    ///
+    /// Returns void, if no `msg` given. Otherwise, returns promise calls to `nft_on_approve`
+    fn nft_approve_batch(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) -> Option<Vec<Promise>>;
+
     /// Revoke an approved account for a specific token.
     ///
     /// Requirements
@@ -69,6 +84,12 @@ pub trait NonFungibleTokenApproval {
     /// * `token_id`: the token with approvals to revoke
     fn nft_revoke_all(&mut self, token_id: TokenId);
 
+    /// Revoke all approved accounts for each of a list of tokens (at most `MAX_TOKENS_PER_REVOKE`), all of which must be owned
+    /// by the caller, e.g. to delist a whole series.
+    ///
+    /// Requires a deposit of 1 yoctoⓃ. Storage for all revoked approvals is refunded to the owner at once
+    fn nft_revoke_all_batch(&mut self, token_ids: Vec<TokenId>);
+
     /// Check if a token is approved for transfer by a given account, optionally
     /// checking an approval_id
     ///
@@ -113,14 +134,15 @@ pub trait NonFungibleTokenApprovalReceiver {
 }
 
 const GAS_FOR_NFT_APPROVE: Gas = Gas(15_000_000_000_000);
+const MIN_GAS_FOR_NFT_ON_APPROVE: Gas = Gas(5_000_000_000_000);
 const NO_DEPOSIT: Balance = 0;
 
 fn expect_token_found<T>(option: Option<T>) -> T {
-    option.unwrap_or_else(|| env::panic_str("Token not found"))
+    option.unwrap_or_else(|| env::panic_str(ERR_TOKEN_NOT_FOUND))
 }
 
 fn expect_approval<T>(option: Option<T>) -> T {
-    option.unwrap_or_else(|| env::panic_str("next_approval_by_id must be set for approval ext"))
+    option.unwrap_or_else(|| env::panic_str(ERR_NEXT_APPROVAL_ID_UNSET))
 }
 
 #[ext_contract(ext_approval_receiver)]
@@ -145,33 +167,11 @@ impl NonFungibleTokenApproval for Contract {
         msg: Option<String>,
     ) -> Option<Promise> {
         assert_at_least_one_yocto();
-        let tokens = self.tokens_mut();
-        let approvals_by_id = tokens
-            .approvals_by_id
-            .as_mut()
-            .unwrap_or_else(|| env::panic_str("NFT does not support Approval Management"));
-
-        let owner_id = expect_token_found(tokens.owner_by_id.get(&token_id));
-
-        require!(env::predecessor_account_id() == owner_id, "Predecessor must be token owner.");
-
-        let next_approval_id_by_id = expect_approval(tokens.next_approval_id_by_id.as_mut());
-        // update HashMap of approvals for this token
-        let approved_account_ids = &mut approvals_by_id.get(&token_id).unwrap_or_default();
-        let approval_id: u64 = next_approval_id_by_id.get(&token_id).unwrap_or(1u64);
-        let old_approval_id = approved_account_ids.insert(account_id.clone(), approval_id);
-
-        // save updated approvals HashMap to contract's LookupMap
-        approvals_by_id.insert(&token_id, approved_account_ids);
-
-        // increment next_approval_id for this token
-        next_approval_id_by_id.insert(&token_id, &(approval_id + 1));
+        let (owner_id, approval_id, storage_used) = self.internal_approve(&token_id, &account_id);
 
         // If this approval replaced existing for same account, no storage was used.
         // Otherwise, require that enough deposit was attached to pay for storage, and refund
         // excess.
-        let storage_used =
-            if old_approval_id.is_none() { bytes_for_approved_account_id(&account_id) } else { 0 };
         refund_deposit(storage_used);
 
         // if given `msg`, schedule call to `nft_on_approve` and return it. Else, return None.
@@ -188,18 +188,79 @@ impl NonFungibleTokenApproval for Contract {
         })
     }
 
+    #[payable]
+    fn nft_approve_batch(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) -> Option<Vec<Promise>> {
+        assert_at_least_one_yocto();
+        require!(!token_ids.is_empty(), ERR_TOKEN_IDS_EMPTY);
+        require!(
+            token_ids.len() <= MAX_TOKENS_PER_APPROVE,
+            format!("token_ids must be less than or equal to {}", MAX_TOKENS_PER_APPROVE)
+        );
+
+        // verify ownership of every token before mutating any state
+        let predecessor_account_id = env::predecessor_account_id();
+        for token_id in token_ids.iter() {
+            let owner_id = expect_token_found(self.tokens().owner_by_id.get(token_id));
+            require!(predecessor_account_id == owner_id, format!("{} {}.", ERR_NOT_TOKEN_OWNER_OF, token_id));
+        }
+
+        let mut storage_used = 0;
+        let mut approvals = Vec::new();
+        for token_id in token_ids {
+            let (owner_id, approval_id, token_storage_used) = self.internal_approve(&token_id, &account_id);
+            storage_used += token_storage_used;
+            approvals.push((token_id, owner_id, approval_id));
+        }
+
+        // charge storage for all approvals at once, and refund excess
+        refund_deposit(storage_used);
+
+        // if given `msg`, schedule a call to `nft_on_approve` for each token and return them. Else, return None.
+        msg.map(|msg| {
+            // gas used so far grows with the number of approvals, so split only what is left (less a reserve for scheduling the calls)
+            let gas_for_calls = env::prepaid_gas().0
+                .checked_sub(env::used_gas().0)
+                .and_then(|gas| gas.checked_sub(GAS_FOR_NFT_APPROVE.0))
+                .unwrap_or_else(|| env::panic_str("Not enough gas attached to call nft_on_approve"));
+            let gas_per_call = Gas(gas_for_calls / approvals.len() as u64);
+            require!(
+                gas_per_call >= MIN_GAS_FOR_NFT_ON_APPROVE,
+                format!("Not enough gas attached to call nft_on_approve for {} tokens", approvals.len())
+            );
+            approvals
+                .into_iter()
+                .map(|(token_id, owner_id, approval_id)| {
+                    ext_approval_receiver::nft_on_approve(
+                        token_id,
+                        owner_id,
+                        approval_id,
+                        msg.clone(),
+                        account_id.clone(),
+                        NO_DEPOSIT,
+                        gas_per_call,
+                    )
+                })
+                .collect()
+        })
+    }
+
     #[payable]
     fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId) {
         assert_one_yocto();
         let tokens = self.tokens_mut();
         let approvals_by_id = tokens.approvals_by_id.as_mut().unwrap_or_else(|| {
-            env::panic_str("NFT does not support Approval Management");
+            env::panic_str(ERR_APPROVALS_NOT_SUPPORTED);
         });
 
         let owner_id = expect_token_found(tokens.owner_by_id.get(&token_id));
         let predecessor_account_id = env::predecessor_account_id();
 
-        require!(predecessor_account_id == owner_id, "Predecessor must be token owner.");
+        require!(predecessor_account_id == owner_id, ERR_NOT_TOKEN_OWNER);
 
         // if token has no approvals, do nothing
         if let Some(approved_account_ids) = &mut approvals_by_id.get(&token_id) {
@@ -225,13 +286,13 @@ impl NonFungibleTokenApproval for Contract {
         assert_one_yocto();
         let tokens = self.tokens_mut();
         let approvals_by_id = tokens.approvals_by_id.as_mut().unwrap_or_else(|| {
-            env::panic_str("NFT does not support Approval Management");
+            env::panic_str(ERR_APPROVALS_NOT_SUPPORTED);
         });
 
         let owner_id = expect_token_found(tokens.owner_by_id.get(&token_id));
         let predecessor_account_id = env::predecessor_account_id();
 
-        require!(predecessor_account_id == owner_id, "Predecessor must be token owner.");
+        require!(predecessor_account_id == owner_id, ERR_NOT_TOKEN_OWNER);
 
         // if token has no approvals, do nothing
         if let Some(approved_account_ids) = &mut approvals_by_id.get(&token_id) {
@@ -242,6 +303,41 @@ impl NonFungibleTokenApproval for Contract {
         }
     }
 
+    #[payable]
+    fn nft_revoke_all_batch(&mut self, token_ids: Vec<TokenId>) {
+        assert_one_yocto();
+        require!(!token_ids.is_empty(), ERR_TOKEN_IDS_EMPTY);
+        require!(
+            token_ids.len() <= MAX_TOKENS_PER_REVOKE,
+            format!("token_ids must be less than or equal to {}", MAX_TOKENS_PER_REVOKE)
+        );
+        let tokens = self.tokens_mut();
+        let approvals_by_id = tokens.approvals_by_id.as_mut().unwrap_or_else(|| {
+            env::panic_str(ERR_APPROVALS_NOT_SUPPORTED);
+        });
+
+        // verify ownership of every token before mutating any state
+        let predecessor_account_id = env::predecessor_account_id();
+        for token_id in token_ids.iter() {
+            let owner_id = expect_token_found(tokens.owner_by_id.get(token_id));
+            require!(predecessor_account_id == owner_id, format!("{} {}.", ERR_NOT_TOKEN_OWNER_OF, token_id));
+        }
+
+        // remove whole HashMap of approvals for each token (tokens without approvals are skipped)...
+        let revoked: Vec<HashMap<AccountId, u64>> = token_ids
+            .iter()
+            .filter_map(|token_id| approvals_by_id.remove(token_id))
+            .collect();
+
+        // ...and refund owner for storage costs of all of them at once
+        if !revoked.is_empty() {
+            refund_approved_account_ids_iter(
+                predecessor_account_id,
+                revoked.iter().flat_map(|approved_account_ids| approved_account_ids.keys()),
+            );
+        }
+    }
+
     fn nft_is_approved(
         &self,
         token_id: TokenId,
@@ -279,4 +375,96 @@ impl NonFungibleTokenApproval for Contract {
             true
         }
     }
-}
\ No newline at end of file
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Get storage cost (in yoctoⓃ) locked by the owner of `token_id` for its approvals, i.e. the amount
+    /// refunded to the owner by `nft_revoke_all`
+    pub fn storage_locked_for_token(&self, token_id: TokenId) -> U128 {
+        let tokens = self.tokens();
+        expect_token_found(tokens.owner_by_id.get(&token_id));
+
+        let storage_bytes: u64 = tokens
+            .approvals_by_id
+            .as_ref()
+            .and_then(|approvals_by_id| approvals_by_id.get(&token_id))
+            .map(|approved_account_ids| approved_account_ids.keys().map(bytes_for_approved_account_id).sum())
+            .unwrap_or(0);
+
+        U128(Balance::from(storage_bytes) * env::storage_byte_cost())
+    }
+
+    /// Emergency override: revoke all approvals for `token_id` regardless of its owner, e.g. if a buggy or malicious market left an
+    /// approval the token owner can't revoke. Contract owner only; use sparingly, as it invalidates any listings of the token.
+    ///
+    /// Requires a deposit of 1 yoctoⓃ. Storage for the revoked approvals is refunded to the token's current owner, who paid for it
+    #[payable]
+    pub fn nft_admin_revoke_all(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+        let tokens = self.tokens_mut();
+        let approvals_by_id = tokens.approvals_by_id.as_mut().unwrap_or_else(|| {
+            env::panic_str(ERR_APPROVALS_NOT_SUPPORTED);
+        });
+        let owner_id = expect_token_found(tokens.owner_by_id.get(&token_id));
+
+        if let Some(approved_account_ids) = approvals_by_id.remove(&token_id) {
+            refund_approved_account_ids(owner_id, &approved_account_ids);
+        }
+    }
+
+    /// Get all accounts approved for `token_id`, mapped to their approval IDs (empty if there are none)
+    pub fn nft_token_approvals(&self, token_id: TokenId) -> HashMap<AccountId, u64> {
+        let tokens = self.tokens();
+        expect_token_found(tokens.owner_by_id.get(&token_id));
+
+        tokens
+            .approvals_by_id
+            .as_ref()
+            .and_then(|approvals_by_id| approvals_by_id.get(&token_id))
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    /// Approve `account_id` (other than the owner) for `token_id`, which must be owned by the predecessor.
+    ///
+    /// Returns token owner, new approval ID and storage used (in bytes) for the caller to charge for.
+    pub(crate) fn internal_approve(&mut self, token_id: &TokenId, account_id: &AccountId) -> (AccountId, u64, u64) {
+        let owner_id = expect_token_found(self.tokens().owner_by_id.get(token_id));
+
+        require!(env::predecessor_account_id() == owner_id, ERR_NOT_TOKEN_OWNER);
+        // owner can already transfer; approving them would only waste storage
+        require!(account_id != &owner_id, ERR_APPROVE_OWNER);
+
+        let (approval_id, storage_used) = self.internal_add_approval(token_id, account_id);
+        (owner_id, approval_id, storage_used)
+    }
+
+    /// Approve `account_id` for `token_id` without checking the predecessor (e.g. type's market on mint). Returns approval ID & storage used
+    pub(crate) fn internal_add_approval(&mut self, token_id: &TokenId, account_id: &AccountId) -> (u64, u64) {
+        let tokens = self.tokens_mut();
+        let approvals_by_id = tokens
+            .approvals_by_id
+            .as_mut()
+            .unwrap_or_else(|| env::panic_str(ERR_APPROVALS_NOT_SUPPORTED));
+        let next_approval_id_by_id = expect_approval(tokens.next_approval_id_by_id.as_mut());
+        // update HashMap of approvals for this token
+        let approved_account_ids = &mut approvals_by_id.get(token_id).unwrap_or_default();
+        let approval_id: u64 = next_approval_id_by_id.get(token_id).unwrap_or(1u64);
+        let old_approval_id = approved_account_ids.insert(account_id.clone(), approval_id);
+
+        // save updated approvals HashMap to contract's LookupMap
+        approvals_by_id.insert(token_id, approved_account_ids);
+
+        // increment next_approval_id for this token
+        next_approval_id_by_id.insert(token_id, &(approval_id + 1));
+
+        // If this approval replaced existing for same account, no storage was used.
+        let storage_used =
+            if old_approval_id.is_none() { bytes_for_approved_account_id(account_id) } else { 0 };
+
+        (approval_id, storage_used)
+    }
+}
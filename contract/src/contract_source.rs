@@ -1,7 +1,7 @@
 use crate::*;
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::{PanicOnDefault};
+use near_sdk::{require, PanicOnDefault};
 
 /// Contract source metadata structure
 /// As per NEP 0330 (https://github.com/near/NEPs/blob/master/neps/nep-0330.md), with addition of `commit_sha`
@@ -34,6 +34,14 @@ pub trait ContractSourceMetadataTrait {
 	fn contract_source_metadata(&self) -> Option<ContractSourceMetadata>;
   /// OWNER-ONLY - Patch/update contract source metadata
   fn patch_contract_source_metadata(&mut self, new_source_metadata: ContractSourceMetadata);
+  /// OWNER-ONLY - Set `version` & `commit_sha` together after a code upgrade (`link` is kept).
+  ///
+  /// Logs a custom event, with schema:
+  /// `EVENT_JSON:{"standard":"nft_series","version":"1.0.0","event":"contract_version_update","data":[{"old_version":"v2.1.0","new_version":"v2.2.0","old_commit_sha":"abc","new_commit_sha":"def"}]}`
+  fn bump_version(&mut self, version: String, commit_sha: String);
+  /// PUBLIC - View (`nft_metadata().spec`, `contract_source_metadata().version`, `contract_source_metadata().commit_sha`) in one call,
+  /// e.g. for compatibility checks
+  fn nft_version_info(&self) -> (String, Option<String>, Option<String>);
 }
 
 #[near_bindgen]
@@ -51,7 +59,7 @@ impl ContractSourceMetadataTrait for Contract {
     fn patch_contract_source_metadata(&mut self, new_source_metadata: ContractSourceMetadata) {
       let initial_storage_usage = env::storage_usage();
 			let owner_id = env::predecessor_account_id();
-			assert_eq!(owner_id.clone(), self.tokens().owner_id, "Unauthorized");
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
 
       let source_metadata_option = self.contract_source_metadata.get();
       let mut source_metadata = if source_metadata_option.is_some() {
@@ -74,7 +82,48 @@ impl ContractSourceMetadataTrait for Contract {
       }
       self.contract_source_metadata.set(&VersionedContractSourceMetadata::from(VersionedContractSourceMetadata::Current(source_metadata)));
       
-      let amt_to_refund = if env::storage_usage() > initial_storage_usage { env::storage_usage() - initial_storage_usage } else { initial_storage_usage - env::storage_usage() };
-			refund_deposit(amt_to_refund);
+      refund_or_reward_storage(initial_storage_usage);
+    }
+
+    #[payable]
+    fn bump_version(&mut self, version: String, commit_sha: String) {
+      let initial_storage_usage = env::storage_usage();
+      assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+      require!(!version.is_empty() && !commit_sha.is_empty(), "version and commit_sha must not be empty");
+
+      let (old_version, old_commit_sha, link) = match self.contract_source_metadata() {
+        Some(source_metadata) => (source_metadata.version, source_metadata.commit_sha, source_metadata.link),
+        None => (None, None, None),
+      };
+      let source_metadata = ContractSourceMetadata {
+        version: Some(version.clone()),
+        commit_sha: Some(commit_sha.clone()),
+        link,
+      };
+      self.contract_source_metadata.set(&VersionedContractSourceMetadata::Current(source_metadata));
+
+      refund_or_reward_storage(initial_storage_usage);
+
+      env::log_str(format!("{}{}", EVENT_JSON, json!({
+        "standard": CUSTOM_EVENT_STANDARD,
+        "version": CUSTOM_EVENT_VERSION,
+        "event": "contract_version_update",
+        "data": [
+          {
+            "old_version": old_version,
+            "new_version": version,
+            "old_commit_sha": old_commit_sha,
+            "new_commit_sha": commit_sha,
+          }
+        ]
+      })).as_ref());
+    }
+
+    fn nft_version_info(&self) -> (String, Option<String>, Option<String>) {
+      let spec = self.metadata.get().unwrap().spec;
+      match self.contract_source_metadata() {
+        Some(source_metadata) => (spec, source_metadata.version, source_metadata.commit_sha),
+        None => (spec, None, None),
+      }
     }
 }
\ No newline at end of file
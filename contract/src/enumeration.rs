@@ -1,14 +1,22 @@
 use crate::*;
 
 use near_sdk::json_types::{U128};
+use std::ops::Bound;
 
 /// "getter" methods for Contract
+///
+/// `limit` of paginated methods defaults to `MAX_ENUMERATION_LIMIT` (500), and may not exceed it
 trait NonFungibleTokenEnumeration {
   /// get total number of NFTs minted across all series (types) in this contract
   fn nft_total_supply(&self) -> U128;
 
-  /// get token objects for all NFTs on this contract, using `from_index` as starting point (if provided) and limiting count to `limit` (if provided)
-  fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token>;
+  /// get token objects for all NFTs on this contract, using `from_index` as starting point (if provided) and limiting count to `limit` (if provided).
+  /// Ordered ascending by token_id, or descending if `reverse` is true (`from_index` then counts from the last token).
+  /// If `include_unrevealed` is false (default true), NFTs of unrevealed types are omitted, and `from_index` & `limit` apply to the filtered list
+  fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>, reverse: Option<bool>, include_unrevealed: Option<bool>) -> Vec<Token>;
+
+  /// get total number of NFTs burned across all series (types) in this contract. NFTs ever minted = `nft_total_supply` + `nft_burned_supply`
+  fn nft_burned_supply(&self) -> U128;
 
   /// get all token IDs on this contract, using `from_index` as starting point (if provided) and limiting count to `limit` (if provided).
   /// Added for the purposes of upgrading metadata for existing tokens
@@ -17,6 +25,9 @@ trait NonFungibleTokenEnumeration {
   /// get number of NFTs owned by a specified owner (across all series/types)
   fn nft_supply_for_owner(self, account_id: AccountId) -> U128;
 
+  /// get number of NFTs in circulation, i.e. `nft_total_supply` minus NFTs owned by `exclude` (e.g. the minting treasury)
+  fn nft_circulating_supply(&self, exclude: AccountId) -> U128;
+
   /// get token objects for all NFTs owned by a specified owner (across all series/types)
   fn nft_tokens_for_owner(
     &self,
@@ -28,30 +39,137 @@ trait NonFungibleTokenEnumeration {
   /// get info on a specific type/series, by title
   fn nft_get_type(&self, token_type_title: TokenTypeTitle) -> TokenTypeJson;
 
-  /// get type format as [TOKEN_DELIMETER, TITLE_DELIMETER, EDITION_DELIMETER]
-  fn nft_get_type_format(&self) -> (char, &'static str, &'static str);
+  /// get info on a specific type/series by its numeric ID (the prefix of its NFTs' token_ids)
+  fn nft_get_type_by_id(&self, token_type_id: TokenTypeId) -> TokenTypeJson;
+
+  /// get the edition number (token_id suffix) the next NFT minted of a specific type/series will be assigned (skipping reserved
+  /// editions), e.g. to show "you'll receive edition #N" before minting. NB: may exceed `copies` if the type's supply is maxed
+  fn nft_next_edition(&self, token_type_title: TokenTypeTitle) -> U64;
+
+  /// get all assets of a specific type/series (by title) with supply remaining, as [filename, supply_remaining, extra], with the
+  /// asset's hex-encoded sha256 appended if it was provided on creation
+  fn nft_get_type_assets(&self, token_type_title: TokenTypeTitle) -> TokenTypeAssets;
+
+  /// get (filename, supply_remaining) of each asset of a specific type/series, e.g. for "3 traits left" on drop pages.
+  /// Sold-out assets are excluded (they're removed from the type's assets once their supply is exhausted), as are all assets of capped types
+  fn nft_type_asset_supply(&self, token_type_title: TokenTypeTitle) -> Vec<(String, u64)>;
+
+  /// get number of NFTs of a specific type/series ever minted to `account_id` (by any mint method, incl. NFTs recategorized into
+  /// the type), whether or not it still holds them. NB: only counts NFTs minted after this count was introduced
+  fn nft_minted_count(&self, token_type_title: TokenTypeTitle, account_id: AccountId) -> U64;
+
+  /// get type format as [TOKEN_DELIMETER, title delimiter, edition delimiter]
+  fn nft_get_type_format(&self) -> (char, String, String);
 
-  /// get info on all types/series contained within this contract
+  /// whether `account_id` may mint NFTs (`nft_mint_type`, `nft_batch_mint_type`) on this contract. Currently only the contract owner can
+  fn nft_is_minter(&self, account_id: AccountId) -> bool;
+
+  /// get info on all types/series contained within this contract. If `only_enabled` / `only_revealed` is true, types that are
+  /// disabled (see `nft_set_type_enabled`) / unrevealed (see `nft_set_type_revealed`) are skipped, in which case `from_index` & `limit`
+  /// apply to the filtered list
   fn nft_get_types(
     &self,
     from_index: Option<U128>,
+    limit: Option<u64>,
+    only_enabled: Option<bool>,
+    only_revealed: Option<bool>,
+  ) -> Vec<TokenTypeJson>;
+
+  /// get info on all types/series owned by `owner_id`. `from_index` & `limit` apply to the filtered list.
+  /// NB: iterates over all types on the contract (O(n) in total number of types), so may exceed gas limits on contracts with many types
+  fn nft_get_types_by_owner(
+    &self,
+    owner_id: AccountId,
+    from_index: Option<U128>,
     limit: Option<u64>
   ) -> Vec<TokenTypeJson>;
 
+  /// get each royalty recipient across types/series, with their royalty (basis points) summed over types weighted by each type's
+  /// `copies` (or NFTs ever minted, if uncapped). Divide by total weight for a weighted average. `from_index` & `limit` apply to types.
+  /// NB: iterates over all types on the contract (O(n) in total number of types) unless paginated, so may exceed gas limits on contracts with many types
+  fn nft_all_royalty_recipients(
+    &self,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> HashMap<AccountId, U64>;
+
   /// get number of NFTs minted (existing!) for a specified type/series
   fn nft_supply_for_type(
     &self,
     token_type_title: TokenTypeTitle,
   ) -> U64;
 
-  /// get token objects for all NFTs of a specified type/series
+  /// get number of NFTs burned for a specified type/series. NFTs ever minted = `nft_supply_for_type` + `nft_burned_supply_for_type`
+  fn nft_burned_supply_for_type(
+    &self,
+    token_type_title: TokenTypeTitle,
+  ) -> U64;
+
+  /// get token objects for all NFTs of a specified type/series, in mint order (or newest first if `reverse` is true, in which case
+  /// `from_index` counts from the newest token). If `include_unrevealed` is false (default true), nothing is returned while the type is unrevealed
   fn nft_tokens_by_type(
+    &self,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>,
+    reverse: Option<bool>,
+    include_unrevealed: Option<bool>,
+  ) -> Vec<Token>;
+
+  /// get token IDs of all NFTs of a specified type/series, in mint order. Cheaper than `nft_tokens_by_type`, as no metadata is assembled
+  fn nft_token_ids_by_type(
+    &self,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<TokenId>;
+
+  /// get token objects for NFTs of a specified type/series (as `nft_tokens_by_type`), along with the `from_index` of the next page
+  /// (`None` if the returned page is the last)
+  fn nft_tokens_by_type_paged(
+    &self,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> (Vec<Token>, Option<U128>);
+
+  /// get token objects for all NFTs of a specified type/series using a range query over `owner_by_id` bounded by the type's token_id prefix,
+  /// so only this type's tokens are read. NB: tokens are ordered lexicographically by token_id (e.g. "1:10" comes before "1:2")
+  fn nft_tokens_in_type_range(
     &self,
     token_type_title: TokenTypeTitle,
     from_index: Option<U128>,
     limit: Option<u64>
   ) -> Vec<Token>;
 
+  /// get token objects for all NFTs of the specified types/series (at most `MAX_TYPES_PER_QUERY`), in order of `token_type_titles`
+  /// (duplicate titles are ignored). `from_index` and `limit` apply across the merged list
+  fn nft_tokens_by_types(
+    &self,
+    token_type_titles: Vec<TokenTypeTitle>,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<Token>;
+
+  /// get token objects for NFTs owned by `account_id` that belong to any of the specified types/series (at most `MAX_TYPES_PER_QUERY`).
+  /// `from_index` and `limit` apply to the filtered list
+  fn nft_tokens_for_owner_by_types(
+    &self,
+    account_id: AccountId,
+    token_type_titles: Vec<TokenTypeTitle>,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<Token>;
+
+  /// get token objects for NFTs of a specified type/series owned by `account_id`, along with the total number of such NFTs (so
+  /// pagination is deterministic). `from_index` and `limit` apply to the filtered list
+  fn nft_tokens_for_owner_by_type(
+    &self,
+    account_id: AccountId,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> (Vec<Token>, U64);
 }
 
 #[near_bindgen]
@@ -60,8 +178,12 @@ impl NonFungibleTokenEnumeration for Contract {
   fn nft_total_supply(&self) -> U128 {
     (self.tokens().owner_by_id.len() as u128).into()
   }
+
+  fn nft_burned_supply(&self) -> U128 {
+    (self.num_burned as u128).into()
+  }
   
-  fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
+  fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>, reverse: Option<bool>, include_unrevealed: Option<bool>) -> Vec<Token> {
     // Get starting index, whether or not it was explicitly given.
     // Defaults to 0 based on the spec:
     // https://nomicon.io/Standards/NonFungibleToken/Enumeration.html#interface
@@ -71,13 +193,20 @@ impl NonFungibleTokenEnumeration for Contract {
         (tokens.owner_by_id.len() as u128) >= start_index,
         "Out of bounds, please use a smaller from_index."
     );
-    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-    assert_ne!(limit, 0, "Cannot provide limit of 0.");
-    tokens.owner_by_id
-        .iter()
+    let limit = enumeration_limit(limit);
+    let token_ids: Box<dyn Iterator<Item = (TokenId, AccountId)>> = if reverse.unwrap_or(false) {
+      Box::new(tokens.owner_by_id.iter_rev())
+    } else {
+      Box::new(tokens.owner_by_id.iter())
+    };
+    let include_unrevealed = include_unrevealed.unwrap_or(true);
+    // many tokens share a type, so only look up each type once
+    let mut revealed_by_type: HashMap<TokenTypeId, bool> = HashMap::new();
+    token_ids
+        .filter(|(token_id, _)| include_unrevealed || self.internal_is_token_revealed(token_id, &mut revealed_by_type))
         .skip(start_index as usize)
         .take(limit)
-        .map(|(token_id, _)| self.nft_token(token_id).unwrap())
+        .filter_map(|(token_id, _)| self.nft_token(token_id))
         .collect()
   }
 
@@ -92,8 +221,7 @@ impl NonFungibleTokenEnumeration for Contract {
         (tokens.owner_by_id.len() as u128) >= start_index,
         "Out of bounds, please use a smaller from_index."
     );
-    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-    assert_ne!(limit, 0, "Cannot provide limit of 0.");
+    let limit = enumeration_limit(limit);
     tokens.owner_by_id
         .iter()
         .skip(start_index as usize)
@@ -111,6 +239,15 @@ impl NonFungibleTokenEnumeration for Contract {
           .map(|account_tokens| U128::from(account_tokens.len() as u128))
           .unwrap_or(U128(0))
   }
+
+  fn nft_circulating_supply(&self, exclude: AccountId) -> U128 {
+      let tokens = self.tokens();
+      let tokens_per_owner = tokens.tokens_per_owner.as_ref().expect(
+          "Could not find tokens_per_owner when calling a method on the enumeration standard.",
+      );
+      let excluded = tokens_per_owner.get(&exclude).map(|account_tokens| account_tokens.len()).unwrap_or(0);
+      U128::from((tokens.owner_by_id.len() - excluded) as u128)
+  }
   
   fn nft_tokens_for_owner(
         &self,
@@ -126,8 +263,7 @@ impl NonFungibleTokenEnumeration for Contract {
         } else {
             return vec![];
         };
-        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-        assert_ne!(limit, 0, "Cannot provide limit of 0.");
+        let limit = enumeration_limit(limit);
         let start_index: u128 = from_index.map(From::from).unwrap_or_default();
         assert!(
             token_set.len() as u128 > start_index,
@@ -137,91 +273,380 @@ impl NonFungibleTokenEnumeration for Contract {
             .iter()
             .skip(start_index as usize)
             .take(limit)
-            .map(|token_id| self.nft_token(token_id).unwrap())
+            .filter_map(|token_id| self.nft_token(token_id))
             .collect()
   }
   
   /// CUSTOM VIEWS for typed tokens
   
   fn nft_get_type(&self, token_type_title: TokenTypeTitle) -> TokenTypeJson {
-    let versioned_token_type = self.token_type_by_id.get(&self.token_type_by_title.get(&token_type_title).expect("no type title")).expect("no type ID");
-		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
-    if token_type.cover_asset.is_some() { 
-      token_type.metadata.media = Some(format!("{}/{}", token_type.metadata.media.unwrap(), token_type.cover_asset.unwrap())) 
-    };
-    TokenTypeJson {
-      metadata: token_type.metadata,
-      owner_id: token_type.owner_id,
-      royalty: token_type.royalty,
-    }
+    let versioned_token_type = self.token_type_by_id.get(&self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE)).expect(ERR_NO_TYPE);
+    TokenTypeJson::from(versioned_token_type_to_token_type(versioned_token_type))
+  }
+
+  fn nft_get_type_by_id(&self, token_type_id: TokenTypeId) -> TokenTypeJson {
+    let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+    TokenTypeJson::from(versioned_token_type_to_token_type(versioned_token_type))
   }
   
-  fn nft_get_type_format(&self) -> (char, &'static str, &'static str) {
-    (TOKEN_DELIMETER, TITLE_DELIMETER, EDITION_DELIMETER)
+  fn nft_next_edition(&self, token_type_title: TokenTypeTitle) -> U64 {
+    let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+    let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+    let token_type = versioned_token_type_to_token_type(versioned_token_type);
+    self.internal_next_sequential_edition(token_type_id, &token_type).into()
+  }
+
+  fn nft_get_type_assets(&self, token_type_title: TokenTypeTitle) -> TokenTypeAssets {
+    let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+    let mut assets = self.token_type_assets_by_id.get(&token_type_id).unwrap_or_default();
+    if let Some(hashes) = self.asset_hashes_by_type.get(&token_type_id) {
+      for asset_detail in assets.iter_mut() {
+        if let Some(hash) = hashes.get(&asset_detail[0]) {
+          asset_detail.push(hash.clone());
+        }
+      }
+    }
+    assets
+  }
+
+  fn nft_minted_count(&self, token_type_title: TokenTypeTitle, account_id: AccountId) -> U64 {
+    let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+    self.minted_to.get(&(token_type_id, account_id)).unwrap_or(0).into()
+  }
+
+  fn nft_type_asset_supply(&self, token_type_title: TokenTypeTitle) -> Vec<(String, u64)> {
+    let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+    self.token_type_assets_by_id.get(&token_type_id)
+      .unwrap_or_default()
+      .into_iter()
+      .map(|asset_detail| (asset_detail[0].clone(), asset_detail[1].parse().unwrap_or(0)))
+      .collect()
+  }
+
+  fn nft_get_type_format(&self) -> (char, String, String) {
+    (TOKEN_DELIMETER, self.title_delimeter.clone(), self.edition_delimeter.clone())
+  }
+
+  fn nft_is_minter(&self, account_id: AccountId) -> bool {
+    account_id == self.tokens().owner_id
   }
   
   fn nft_get_types(
     &self,
     from_index: Option<U128>,
-    limit: Option<u64>
+    limit: Option<u64>,
+    only_enabled: Option<bool>,
+    only_revealed: Option<bool>,
     ) -> Vec<TokenTypeJson> {
         let start_index: u128 = from_index.map(From::from).unwrap_or_default();
         assert!(
             (self.token_type_by_id.len() as u128) >= start_index,
             "Out of bounds, please use a smaller from_index."
         );
-        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-        assert_ne!(limit, 0, "Cannot provide limit of 0.");
+        let limit = enumeration_limit(limit);
+        let only_enabled = only_enabled.unwrap_or(false);
+        let only_revealed = only_revealed.unwrap_or(false);
         
     let token_types = self.token_type_by_id.iter()
+      .map(|(_, versioned_token_type)| versioned_token_type_to_token_type(versioned_token_type))
+      .filter(|token_type| (!only_enabled || token_type.enabled) && (!only_revealed || token_type.revealed))
       .skip(start_index as usize)
       .take(limit)
-      .map(|(_, versioned_token_type)| {
-        let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
-        if token_type.cover_asset.is_some() { 
-          token_type.metadata.media = Some(format!("{}/{}", token_type.metadata.media.unwrap(), token_type.cover_asset.unwrap())) 
-        };
-        TokenTypeJson {
-          metadata: token_type.metadata,
-          owner_id: token_type.owner_id,
-          royalty: token_type.royalty,
-        }
-      })
+      .map(TokenTypeJson::from)
       .collect();
       token_types
   }
+
+  fn nft_all_royalty_recipients(
+    &self,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> HashMap<AccountId, U64> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let limit = enumeration_limit(limit);
+
+    let mut weighted_royalty: HashMap<AccountId, u64> = HashMap::new();
+    for (_, versioned_token_type) in self.token_type_by_id.iter().skip(start_index as usize).take(limit) {
+      let token_type = versioned_token_type_to_token_type(versioned_token_type);
+      let weight = token_type.metadata.copies.unwrap_or_else(|| token_type.num_minted());
+      for (account_id, royalty) in token_type.royalty.iter() {
+        *weighted_royalty.entry(account_id.clone()).or_default() += *royalty as u64 * weight;
+      }
+    }
+    weighted_royalty.into_iter().map(|(account_id, weighted)| (account_id, weighted.into())).collect()
+  }
+
+  fn nft_get_types_by_owner(
+    &self,
+    owner_id: AccountId,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<TokenTypeJson> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let limit = enumeration_limit(limit);
+
+    self.token_type_by_id.iter()
+      .map(|(_, versioned_token_type)| versioned_token_type_to_token_type(versioned_token_type))
+      .filter(|token_type| token_type.owner_id == owner_id)
+      .skip(start_index as usize)
+      .take(limit)
+      .map(TokenTypeJson::from)
+      .collect()
+  }
   
   fn nft_supply_for_type(
         &self,
         token_type_title: TokenTypeTitle,
     ) -> U64 {
-        let versioned_token_type = self.token_type_by_id.get(&self.token_type_by_title.get(&token_type_title).expect("no type")).expect("no type");
-        let token_type = versioned_token_type_to_token_type(versioned_token_type);
+        let token_type = self.internal_token_type_by_title(&token_type_title);
         token_type.tokens.len().into()
   }
+
+  fn nft_burned_supply_for_type(
+        &self,
+        token_type_title: TokenTypeTitle,
+    ) -> U64 {
+        let token_type = self.internal_token_type_by_title(&token_type_title);
+        token_type.num_burned.into()
+  }
   
   fn nft_tokens_by_type(
     &self,
     token_type_title: TokenTypeTitle,
     from_index: Option<U128>,
-    limit: Option<u64>
+    limit: Option<u64>,
+    reverse: Option<bool>,
+    include_unrevealed: Option<bool>,
   ) -> Vec<Token> {
     let start_index: u128 = from_index.map(From::from).unwrap_or_default();
-    let versioned_token_type = self.token_type_by_id.get(&self.token_type_by_title.get(&token_type_title).expect("no type")).expect("no type");
-    let token_type = versioned_token_type_to_token_type(versioned_token_type);
+    let token_type = self.internal_token_type_by_title(&token_type_title);
+    if !token_type.revealed && !include_unrevealed.unwrap_or(true) {
+      return vec![];
+    }
     let tokens = token_type.tokens;
+    // a type with nothing minted (yet) is an empty page, whatever the `from_index`
+    if tokens.is_empty() {
+      return vec![];
+    }
     assert!(
         (tokens.len() as u128) >= start_index,
         "Out of bounds, please use a smaller from_index."
     );
-    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-    assert_ne!(limit, 0, "Cannot provide limit of 0.");
-    
+    let limit = enumeration_limit(limit);
+    let token_ids: Box<dyn Iterator<Item = TokenId>> = if reverse.unwrap_or(false) {
+      Box::new(tokens.iter().rev())
+    } else {
+      Box::new(tokens.iter())
+    };
+
+    token_ids
+      .skip(start_index as usize)
+      .take(limit)
+      .filter_map(|token_id| self.nft_token(token_id))
+      .collect()
+  }
+
+  fn nft_token_ids_by_type(
+    &self,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<TokenId> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let token_type = self.internal_token_type_by_title(&token_type_title);
+    let tokens = token_type.tokens;
+    // as in `nft_tokens_by_type`: unminted type => empty
+    if tokens.is_empty() {
+      return vec![];
+    }
+    assert!(
+        (tokens.len() as u128) >= start_index,
+        "Out of bounds, please use a smaller from_index."
+    );
+    let limit = enumeration_limit(limit);
+
     tokens.iter()
       .skip(start_index as usize)
       .take(limit)
-      .map(|token_id| self.nft_token(token_id).unwrap())
       .collect()
   }
   
-}
\ No newline at end of file
+  fn nft_tokens_by_type_paged(
+    &self,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> (Vec<Token>, Option<U128>) {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let total = u128::from(self.nft_supply_for_type(token_type_title.clone()).0);
+    let tokens = self.nft_tokens_by_type(token_type_title, from_index, limit, None, None);
+
+    let next_index = start_index + tokens.len() as u128;
+    let next_from_index = if next_index < total { Some(U128(next_index)) } else { None };
+    (tokens, next_from_index)
+  }
+
+  fn nft_tokens_in_type_range(
+    &self,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<Token> {
+    let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let limit = enumeration_limit(limit);
+
+    // all token_ids of this type are >= "{id}:" and < "{id};" (';' is the character following ':'), so e.g. type 1 excludes type 10
+    let lower_bound = format!("{}{}", token_type_id, TOKEN_DELIMETER);
+    let upper_bound = format!("{}{}", token_type_id, (TOKEN_DELIMETER as u8 + 1) as char);
+
+    self.tokens().owner_by_id
+      .range((Bound::Included(lower_bound), Bound::Excluded(upper_bound)))
+      .skip(start_index as usize)
+      .take(limit)
+      .filter_map(|(token_id, _)| self.nft_token(token_id))
+      .collect()
+  }
+
+  fn nft_tokens_by_types(
+    &self,
+    token_type_titles: Vec<TokenTypeTitle>,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<Token> {
+    assert!(token_type_titles.len() <= MAX_TYPES_PER_QUERY, "Cannot query more than {} types", MAX_TYPES_PER_QUERY);
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let limit = enumeration_limit(limit);
+
+    // resolve all titles up front, so an unknown title panics regardless of pagination
+    let mut token_type_ids: Vec<TokenTypeId> = Vec::new();
+    for token_type_title in token_type_titles.iter() {
+      let token_type_id = self.token_type_by_title.get(token_type_title).expect(ERR_NO_TYPE);
+      if !token_type_ids.contains(&token_type_id) {
+        token_type_ids.push(token_type_id);
+      }
+    }
+    let token_sets: Vec<UnorderedSet<TokenId>> = token_type_ids.iter()
+      .map(|token_type_id| {
+        let versioned_token_type = self.token_type_by_id.get(token_type_id).expect(ERR_NO_TYPE);
+        versioned_token_type_to_token_type(versioned_token_type).tokens
+      })
+      .collect();
+
+    let total: u64 = token_sets.iter().map(|tokens| tokens.len()).sum();
+    assert!(
+        (total as u128) >= start_index,
+        "Out of bounds, please use a smaller from_index."
+    );
+
+    token_sets.iter()
+      .flat_map(|tokens| tokens.iter())
+      .skip(start_index as usize)
+      .take(limit)
+      .filter_map(|token_id| self.nft_token(token_id))
+      .collect()
+  }
+
+  fn nft_tokens_for_owner_by_types(
+    &self,
+    account_id: AccountId,
+    token_type_titles: Vec<TokenTypeTitle>,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> Vec<Token> {
+    assert!(token_type_titles.len() <= MAX_TYPES_PER_QUERY, "Cannot query more than {} types", MAX_TYPES_PER_QUERY);
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let limit = enumeration_limit(limit);
+
+    // resolve all titles up front, so an unknown title panics regardless of the owner's holdings
+    let token_type_ids: Vec<TokenTypeId> = token_type_titles.iter()
+      .map(|token_type_title| self.token_type_by_title.get(token_type_title).expect(ERR_NO_TYPE))
+      .collect();
+
+    let tokens_per_owner = self.tokens().tokens_per_owner.as_ref().expect(
+        "Could not find tokens_per_owner when calling a method on the enumeration standard.",
+    );
+    let token_set = if let Some(token_set) = tokens_per_owner.get(&account_id) {
+        token_set
+    } else {
+        return vec![];
+    };
+    token_set
+      .iter()
+      .filter(|token_id| {
+        token_id.split(TOKEN_DELIMETER).next()
+          .and_then(|token_type_id| token_type_id.parse::<TokenTypeId>().ok())
+          .map_or(false, |token_type_id| token_type_ids.contains(&token_type_id))
+      })
+      .skip(start_index as usize)
+      .take(limit)
+      .filter_map(|token_id| self.nft_token(token_id))
+      .collect()
+  }
+
+  fn nft_tokens_for_owner_by_type(
+    &self,
+    account_id: AccountId,
+    token_type_title: TokenTypeTitle,
+    from_index: Option<U128>,
+    limit: Option<u64>
+  ) -> (Vec<Token>, U64) {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+    let limit = enumeration_limit(limit);
+    let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+
+    let tokens_per_owner = self.tokens().tokens_per_owner.as_ref().expect(
+        "Could not find tokens_per_owner when calling a method on the enumeration standard.",
+    );
+    let token_ids: Vec<TokenId> = match tokens_per_owner.get(&account_id) {
+      Some(token_set) => token_set
+        .iter()
+        .filter(|token_id| {
+          token_id.split(TOKEN_DELIMETER).next()
+            .and_then(|id| id.parse::<TokenTypeId>().ok())
+            .map_or(false, |id| id == token_type_id)
+        })
+        .collect(),
+      None => vec![],
+    };
+    let tokens = token_ids.iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .filter_map(|token_id| self.nft_token(token_id.clone()))
+      .collect();
+    (tokens, U64(token_ids.len() as u64))
+  }
+
+}
+
+impl Contract {
+  /// type/series by title, including V1 types not yet moved into `token_type_by_id`. Panics only if no type has this title
+  fn internal_token_type_by_title(&self, token_type_title: &TokenTypeTitle) -> TokenType {
+    let token_type_id = self.token_type_by_title.get(token_type_title).expect(ERR_NO_TYPE);
+    match self.token_type_by_id.get(&token_type_id) {
+      Some(versioned_token_type) => versioned_token_type_to_token_type(versioned_token_type),
+      None => TokenType::from(self.token_type_by_id_v1.get(&token_type_id).expect(ERR_NO_TYPE)),
+    }
+  }
+
+  /// whether the type of `token_id` is revealed, caching the result per type in `revealed_by_type`. Tokens whose type can't be
+  /// resolved (e.g. legacy token_ids) count as revealed
+  fn internal_is_token_revealed(&self, token_id: &str, revealed_by_type: &mut HashMap<TokenTypeId, bool>) -> bool {
+    let token_type_id = match token_id.split(TOKEN_DELIMETER).next().and_then(|id| id.parse::<TokenTypeId>().ok()) {
+      Some(token_type_id) => token_type_id,
+      None => return true,
+    };
+    *revealed_by_type.entry(token_type_id).or_insert_with(|| {
+      self.token_type_by_id.get(&token_type_id)
+        .map_or(true, |versioned_token_type| versioned_token_type_to_token_type(versioned_token_type).revealed)
+    })
+  }
+}
+
+/// `limit` of an enumeration method, defaulting to (and capped at) `MAX_ENUMERATION_LIMIT` so views stay within gas limits
+fn enumeration_limit(limit: Option<u64>) -> usize {
+  let limit = limit.unwrap_or(MAX_ENUMERATION_LIMIT);
+  assert_ne!(limit, 0, "Cannot provide limit of 0.");
+  assert!(limit <= MAX_ENUMERATION_LIMIT, "Cannot provide limit greater than {}", MAX_ENUMERATION_LIMIT);
+  limit as usize
+}
@@ -0,0 +1,60 @@
+// Panic messages, kept here so they stay stable (and greppable) for integrators matching on them.
+// NB: changing any of these strings is a breaking change. Messages with parameters use the constant as a stable prefix, with details
+// appended at the call site (e.g. `format!("{}: {}", ERR_TOKEN_NOT_FOUND, token_id)`).
+
+// authorization
+pub const ERR_UNAUTHORIZED: &str = "Unauthorized";
+pub const ERR_NOT_TYPE_OWNER: &str = "not type owner";
+pub const ERR_NOT_TOKEN_OWNER: &str = "Predecessor must be token owner.";
+pub const ERR_NOT_TOKEN_OWNER_OF: &str = "Predecessor must be token owner of"; // followed by token_id, for batch methods
+pub const ERR_SENDER_NOT_APPROVED: &str = "Sender not approved";
+
+// lookups
+pub const ERR_NO_TYPE: &str = "no type";
+pub const ERR_NO_ASSETS: &str = "No assets";
+pub const ERR_TOKEN_NOT_FOUND: &str = "Token not found";
+pub const ERR_INVALID_TOKEN_ID: &str = "invalid token_id";
+pub const ERR_TOKENS_PER_OWNER_UNGUARDED: &str = "Unable to access tokens per owner in unguarded call.";
+
+// type creation & updates
+pub const ERR_TITLE_REQUIRED: &str = "token_metadata.title is required";
+pub const ERR_TITLE_EXISTS: &str = "token_metadata.title already exists";
+pub const ERR_COPIES_REQUIRED: &str = "token_metadata.copies is required";
+pub const ERR_COPIES_ZERO: &str = "token_metadata.copies must be greater than 0";
+pub const ERR_COPIES_OVERFLOW: &str = "copies overflow";
+pub const ERR_MEDIA_REQUIRED: &str = "token_metadata.media is required";
+pub const ERR_ASSETS_EMPTY: &str = "assets vector must not be empty";
+pub const ERR_ASSETS_EXCEED_COPIES: &str = "length of assets vector must not exceed copies";
+pub const ERR_NEW_ASSETS_EMPTY: &str = "new_assets must not be empty";
+pub const ERR_TOTAL_SUPPLY_OVERFLOW: &str = "Total supply overflow";
+pub const ERR_COVER_ASSET_EMPTY: &str = "cover_asset must not be empty";
+pub const ERR_TYPE_FROZEN: &str = "type is frozen";
+pub const ERR_TYPE_CAPPED: &str = "type is capped";
+pub const ERR_TYPE_HAS_TOKENS: &str = "Cannot delete a type that contains tokens";
+pub const ERR_TYPE_ALREADY_UPGRADED: &str = "token type has already been upgraded";
+
+// minting
+pub const ERR_TYPE_SUPPLY_MAXED: &str = "type supply maxed";
+pub const ERR_EDITIONS_RESERVED: &str = "remaining editions are reserved";
+pub const ERR_EDITION_OVERFLOW: &str = "edition overflow";
+pub const ERR_NOT_PUBLIC_MINT: &str = "type is not available for public minting";
+pub const ERR_TYPE_DISABLED: &str = "type is disabled";
+pub const ERR_HOLDINGS_CAP_REACHED: &str = "Holdings cap reached.";
+pub const ERR_TOKEN_ALREADY_IN_TYPE: &str = "token already belongs to this type";
+pub const ERR_MISSING_METADATA: &str = "Must provide metadata";
+pub const ERR_TOKEN_ID_NOT_UNIQUE: &str = "token_id must be unique";
+
+// transfers & locks
+pub const ERR_SAME_OWNER: &str = "Current and next owner must differ";
+pub const ERR_OWNER_CANNOT_LOCK: &str = "Token owner cannot be locker";
+pub const ERR_ALREADY_LOCKED: &str = "Token is already locked";
+pub const ERR_NOT_LOCKED: &str = "Token is not locked";
+pub const ERR_TOKEN_LOCKED_BY: &str = "Token is locked by"; // followed by locker_id
+pub const ERR_NOT_LOCKER: &str = "Only the locker or contract owner can unlock a token";
+pub const ERR_RECEIVER_NOT_REGISTERED: &str = "Receiver is not registered for storage (see storage_deposit)";
+
+// approvals
+pub const ERR_APPROVALS_NOT_SUPPORTED: &str = "NFT does not support Approval Management";
+pub const ERR_APPROVE_OWNER: &str = "Token owner and approved account must differ";
+pub const ERR_TOKEN_IDS_EMPTY: &str = "token_ids must not be empty";
+pub const ERR_NEXT_APPROVAL_ID_UNSET: &str = "next_approval_by_id must be set for approval ext";
@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::{U64, U128};
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base64VecU8, U64, U128};
 use near_sdk::{
-	env, near_bindgen, serde_json::json, AccountId, BorshStorageKey, PanicOnDefault, CryptoHash,
+	assert_one_yocto, env, near_bindgen, require, serde_json::json, AccountId, Balance, BorshStorageKey, PanicOnDefault, CryptoHash, Promise,
 };
 use near_sdk::serde::{Deserialize, Serialize};
 
@@ -15,6 +15,8 @@ pub use crate::royalty::*;
 pub use crate::enumeration::*;
 pub use crate::nft_type::*;
 pub use crate::contract_source::*;
+pub use crate::storage::*;
+pub use crate::errors::*;
 
 mod metadata;
 mod nft_core;
@@ -24,6 +26,8 @@ mod royalty;
 mod enumeration;
 mod nft_type;
 mod contract_source;
+mod storage;
+mod errors;
 
 /// CUSTOM TYPES
 
@@ -36,14 +40,38 @@ pub struct Payout {
 
 /// log type const
 pub const EVENT_JSON: &str = "EVENT_JSON:";
+/// max number of type titles accepted by `nft_tokens_by_types`
+pub const MAX_TYPES_PER_QUERY: usize = 10;
+/// default and max `limit` of enumeration methods e.g. `nft_tokens` (keeps views within gas limits)
+pub const MAX_ENUMERATION_LIMIT: u64 = 500;
+/// max number of token_ids accepted by `nft_revoke_all_batch` (keeps it within gas limits)
+pub const MAX_TOKENS_PER_REVOKE: usize = 50;
+/// max number of token_ids accepted by `nft_approve_batch` (keeps it, and its `nft_on_approve` calls, within gas limits)
+pub const MAX_TOKENS_PER_APPROVE: usize = 10;
+/// max number of token_ids accepted by `nft_burn_batch` (keeps it within gas limits)
+pub const MAX_TOKENS_PER_BURN: usize = 50;
+/// max number of NFTs a type may have to be split by `nft_split_type`, which checks each of them (keeps it within gas limits)
+pub const MAX_TOKENS_PER_SPLIT: u64 = 500;
+/// `standard` of custom (non-NEP-171) events logged with `EVENT_JSON` prefix e.g. `nft_delete_type`
+pub const CUSTOM_EVENT_STANDARD: &str = "nft_series";
+/// version of custom events
+pub const CUSTOM_EVENT_VERSION: &str = "1.0.0";
 /// between token_type_id and edition number e.g. 42:2 where 42 is type and 2 is edition
 pub const TOKEN_DELIMETER: char = ':';
-/// TokenMetadata.title returned for individual token e.g. "Title — 2/10" where 10 is max copies
+/// default TokenMetadata.title delimiter returned for individual token e.g. "Title — 2/10" where 10 is max copies (see `Contract.title_delimeter`)
 pub const TITLE_DELIMETER: &str = " — ";
-/// e.g. "Title — 2/10" where 10 is max copies
+/// default edition delimiter e.g. "Title — 2/10" where 10 is max copies (see `Contract.edition_delimeter`)
 pub const EDITION_DELIMETER: &str = "/";
 /// between filename and extension e.g. "cat.jpg" where cat is filename and jpg is extension
 pub const FILE_DELIMETER: char = '.';
+/// `memo` prefix triggering a lazy mint in `nft_transfer_payout` e.g. `LAZY_MINT:{"token_type_title":"Title","receiver_id":"alice.near"}`
+pub const LAZY_MINT_MEMO_PREFIX: &str = "LAZY_MINT:";
+/// denominator of royalty percentages, i.e. royalties are in basis points (10_000 = 100%). Used for both validation & payouts
+pub const ROYALTY_DENOMINATOR: u32 = 10_000;
+/// max number of royalty recipients for a type (keeps `nft_payout` & `nft_transfer_payout` within gas limits)
+pub const MAX_ROYALTY_RECIPIENTS: u32 = 10;
+/// default max number of receivers accepted by `nft_batch_mint_type` (see `Contract.max_batch_size`), i.e. the previously hardcoded cap
+pub const DEFAULT_MAX_BATCH_SIZE: u64 = 1000;
 
 // CONTRACT
 
@@ -56,6 +84,19 @@ pub struct ContractV1 { // OLD
 	token_type_by_id: UnorderedMap<TokenTypeId, TokenTypeV1>,
 }
 
+/// state layout as of v2.1.0, i.e. before `mint_fee` and the fields following it were added to `Contract`. Read by `migrate`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV2 { // OLD
+	tokens_v1: NonFungibleTokenV1,
+	tokens: VersionedNonFungibleToken,
+	metadata: LazyOption<NFTContractMetadata>,
+	contract_source_metadata: LazyOption<VersionedContractSourceMetadata>,
+	token_type_by_title: LookupMap<TokenTypeTitle, TokenTypeId>,
+	token_type_by_id_v1: UnorderedMap<TokenTypeId, TokenTypeV1>,
+	token_type_by_id: UnorderedMap<TokenTypeId, VersionedTokenType>,
+	token_type_assets_by_id: LookupMap<TokenTypeId, TokenTypeAssets>,
+}
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract { // CURRENT
@@ -67,6 +108,26 @@ pub struct Contract { // CURRENT
 	token_type_by_id_v1: UnorderedMap<TokenTypeId, TokenTypeV1>,
 	token_type_by_id: UnorderedMap<TokenTypeId, VersionedTokenType>,
 	token_type_assets_by_id: LookupMap<TokenTypeId, TokenTypeAssets>, // parallel with token_type_by_id - used by minting function to set up NFT
+	// added after v2.1.0 - NB: any new field must also be initialized by `migrate`
+	mint_fee: Option<U128>, // fee paid to contract owner on each `nft_public_mint`, in addition to type price & storage
+	holdings_by_type: LookupMap<(TokenTypeId, AccountId), u64>, // number of NFTs of a type held by an account - used to enforce `max_holdings_per_account`
+	default_royalty: HashMap<AccountId, u32>, // used by `nft_create_type` when no royalty is provided
+	title_delimeter: String, // between title and edition in TokenMetadata.title returned for individual token e.g. "Title — 2/10"
+	edition_delimeter: String, // between edition and copies in TokenMetadata.title returned for individual token e.g. "Title — 2/10"
+	num_burned: u64, // number of NFTs burned across all types (`tokens.owner_by_id` only contains existing NFTs)
+	max_batch_size: u64, // max number of receivers accepted by `nft_batch_mint_type`
+	min_royalty: HashMap<AccountId, u32>, // minimum royalty (basis points) every type must pay each of these accounts e.g. treasury
+	max_copies_per_type: Option<u64>, // if provided, max `copies` of a type enforced by `nft_create_type`
+	locked_by_id: LookupMap<TokenId, AccountId>, // locker (e.g. marketplace holding an auction) of each locked NFT - only the locker can transfer a locked NFT
+	storage_deposits: LookupMap<AccountId, Balance>, // NEP-145 storage balance of each registered account - drawn from to pay for storage of mints
+	storage_deposits_total: Balance, // sum of `storage_deposits` balances, which belong to their accounts so can't be withdrawn via `withdraw_near`
+	reserved_editions: LookupMap<(TokenTypeId, u64), AccountId>, // account for which each (type, edition) was reserved via `nft_reserve_edition`, until claimed - skipped by sequential mints
+	claimed_editions: LookupSet<(TokenTypeId, u64)>, // reserved (type, edition)s that have been claimed - still skipped by sequential mints, even if burned
+	asset_hashes_by_type: LookupMap<TokenTypeId, HashMap<String, String>>, // hex-encoded sha256 of each asset file (by filename), if provided on creation - kept after the asset's supply is exhausted
+	token_id_by_mint_key: LookupMap<String, TokenId>, // NFT minted by each `nft_mint_type` call given a `mint_id`, keyed by "{token_type_id}:{mint_id}" - makes retried mints idempotent
+	require_receiver_registration: bool, // if true, NFTs can only be transferred to accounts registered via `storage_deposit`
+	contract_metadata_extra: Option<String>, // `extra` of contract metadata (JSON string), kept outside `metadata` so its Borsh layout is unchanged
+	minted_to: LookupMap<(TokenTypeId, AccountId), u64>, // number of NFTs of a type ever minted to an account (unlike `holdings_by_type`, unaffected by transfers & burns)
 	// token_type_mint_args_by_id: LookupMap<TokenTypeId, VersionedTokenTypeMintArgs>, // parallel with token_type_by_id - used by minting function to set up NFT
 }
 
@@ -98,6 +159,14 @@ pub enum StorageKey {
 		TokenTypeById2, // ACTIVE - self.token_type_by_id located here
     TokensByTypeInner { token_type_id: u64 },
 		TokenTypeAssetsById,
+		HoldingsByType,
+		LockedById,
+		StorageDeposits,
+		ReservedEditions,
+		ClaimedEditions,
+		AssetHashesByType,
+		TokenIdByMintKey,
+		MintedTo,
 }
 
 #[near_bindgen]
@@ -114,6 +183,7 @@ impl Contract {
                 base_uri: None,
                 reference: None,
                 reference_hash: None,
+                extra: None,
             },
 						"b245d8f7fbe72c250cbabbd16544477f9958be2e".to_string(), // example commit sha
         )
@@ -147,11 +217,72 @@ impl Contract {
 						token_type_by_id: UnorderedMap::new(StorageKey::TokenTypeById2),
 						token_type_by_title: LookupMap::new(StorageKey::TokenTypeByTitle),
 						token_type_assets_by_id: LookupMap::new(StorageKey::TokenTypeAssetsById),
+						mint_fee: None,
+						holdings_by_type: LookupMap::new(StorageKey::HoldingsByType),
+						default_royalty: HashMap::new(),
+						title_delimeter: TITLE_DELIMETER.to_string(),
+						edition_delimeter: EDITION_DELIMETER.to_string(),
+						num_burned: 0,
+						max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+						min_royalty: HashMap::new(),
+						max_copies_per_type: None,
+						locked_by_id: LookupMap::new(StorageKey::LockedById),
+						storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+						storage_deposits_total: 0,
+						reserved_editions: LookupMap::new(StorageKey::ReservedEditions),
+						claimed_editions: LookupSet::new(StorageKey::ClaimedEditions),
+						asset_hashes_by_type: LookupMap::new(StorageKey::AssetHashesByType),
+						token_id_by_mint_key: LookupMap::new(StorageKey::TokenIdByMintKey),
+						require_receiver_registration: false,
+						contract_metadata_extra: metadata.extra.clone(),
+						minted_to: LookupMap::new(StorageKey::MintedTo),
             metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
 						contract_source_metadata: LazyOption::new(StorageKey::SourceMetadata, Some(&VersionedContractSourceMetadata::Current(source_metadata))),
         }
     }
 
+    /// Upgrade state stored by v2.1.0 (see `ContractV2`) to the current layout, initializing every field added since. Must be called
+    /// by the contract owner right after deploying this version over a v2.1.0 contract (until then, state can't be deserialized).
+    /// Panics if state has already been migrated
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: ContractV2 = env::state_read().unwrap_or_else(|| env::panic_str("no v2.1.0 state to migrate"));
+        let owner_id = match &old_state.tokens {
+            VersionedNonFungibleToken::Current(tokens) => tokens.owner_id.clone(),
+        };
+        assert_eq!(env::predecessor_account_id(), owner_id, "{}", ERR_UNAUTHORIZED);
+
+        Self {
+						tokens_v1: old_state.tokens_v1,
+						tokens: old_state.tokens,
+						metadata: old_state.metadata,
+						contract_source_metadata: old_state.contract_source_metadata,
+						token_type_by_title: old_state.token_type_by_title,
+						token_type_by_id_v1: old_state.token_type_by_id_v1,
+						token_type_by_id: old_state.token_type_by_id,
+						token_type_assets_by_id: old_state.token_type_assets_by_id,
+						mint_fee: None,
+						holdings_by_type: LookupMap::new(StorageKey::HoldingsByType), // existing types have no holdings cap
+						default_royalty: HashMap::new(),
+						title_delimeter: TITLE_DELIMETER.to_string(),
+						edition_delimeter: EDITION_DELIMETER.to_string(),
+						num_burned: 0, // burning was not possible before v2.1.0
+						max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+						min_royalty: HashMap::new(),
+						max_copies_per_type: None,
+						locked_by_id: LookupMap::new(StorageKey::LockedById),
+						storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+						storage_deposits_total: 0,
+						reserved_editions: LookupMap::new(StorageKey::ReservedEditions),
+						claimed_editions: LookupSet::new(StorageKey::ClaimedEditions),
+						asset_hashes_by_type: LookupMap::new(StorageKey::AssetHashesByType),
+						token_id_by_mint_key: LookupMap::new(StorageKey::TokenIdByMintKey),
+						require_receiver_registration: false,
+						contract_metadata_extra: None,
+						minted_to: LookupMap::new(StorageKey::MintedTo),
+        }
+    }
+
 		fn tokens(&self) -> &NonFungibleToken {
 			match &self.tokens {
 					VersionedNonFungibleToken::Current(data) => data,
@@ -167,18 +298,24 @@ impl Contract {
 		#[payable]
 		pub fn patch_media_and_assets_for_token_type(&mut self, token_type_title: TokenTypeTitle, media: String, mut assets: Vec<AssetDetail>) {
 			let owner_id = env::predecessor_account_id();
-			assert_eq!(owner_id.clone(), self.tokens().owner_id, "Unauthorized");
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
 			let initial_storage_usage = env::storage_usage();
 			assert!(assets.len() == 1, "Assets must be of length 1"); // existing token types have only one asset
-			let token_type_id = self.token_type_by_title.get(&token_type_title).expect("no type");
+			assert!(assets[0].len() == 3 || assets[0].len() == 4, "Asset must have 3 elements [filename, supply, extra], plus optionally a 4th (sha256 of the asset)");
+			if let Some(hash) = assets[0].get(3) {
+				assert_valid_asset_sha256(0, hash);
+			}
+			let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
 			let mut versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("token type has not been upgraded yet");
 			let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+			assert_not_frozen(&token_type);
 
 			token_type.metadata.media = Some(media);
 			token_type.cover_asset = Some(assets[0][0].clone()); // filename of media asset will serve as cover_asset
 
-			let num_minted = token_type.tokens.len();
-			let supply_remaining = token_type.metadata.copies.unwrap() - num_minted;
+			let num_minted = token_type.num_minted();
+			let copies = token_type.metadata.copies.unwrap_or_else(|| env::panic_str("copies is None"));
+			let supply_remaining = copies.checked_sub(num_minted).unwrap_or_else(|| env::panic_str(&format!("minted exceeds copies ({} minted, {} copies)", num_minted, copies)));
 			// log!(format!("supply remaining: {}", supply_remaining));
 
 			assets[0][1] = supply_remaining.to_string();
@@ -187,9 +324,13 @@ impl Contract {
 			// update token metadata
 			token_type.tokens.iter().for_each(|token_id| {
 				// log!(format!("updating metadata for token with id {}", token_id));
-				let token_metadata_versioned = self.tokens().token_metadata_by_id.as_ref().unwrap().get(&token_id);
-        let mut token_metadata = versioned_token_metadata_to_token_metadata(token_metadata_versioned.unwrap());
+				let token_metadata_versioned = self.tokens().token_metadata_by_id.as_ref()
+					.unwrap_or_else(|| env::panic_str("token metadata not supported"))
+					.get(&token_id)
+					.unwrap_or_else(|| env::panic_str(&format!("no metadata for token {}", token_id)));
+        let mut token_metadata = versioned_token_metadata_to_token_metadata(token_metadata_versioned);
 				token_metadata.media = Some(assets[0][0].clone());
+				token_metadata.updated_at = Some(env::block_timestamp().into());
 				self.tokens_mut().token_metadata_by_id
             .as_mut()
             .and_then(|by_id| by_id.insert(&token_id, &VersionedTokenMetadata::from(VersionedTokenMetadata::Current(token_metadata))));
@@ -204,12 +345,11 @@ impl Contract {
 			// log!(format!("inserting assets for token type {} with id {}", token_type_title, token_type_id));
 			self.token_type_assets_by_id.insert(&token_type_id, &assets);
 
-			let amt_to_refund = if env::storage_usage() > initial_storage_usage { env::storage_usage() - initial_storage_usage } else { initial_storage_usage - env::storage_usage() };
-			refund_deposit(amt_to_refund);
+			refund_or_reward_storage(initial_storage_usage);
 			// log!("done!");
 		}
 
-		/// Update `base_uri` for contract
+		/// Update `base_uri` for contract. Logs a `contract_metadata_update` event if it changed (see `log_contract_metadata_update`)
 		#[payable]
 		pub fn patch_base_uri(
 				&mut self,
@@ -217,17 +357,310 @@ impl Contract {
 		) {
 			let initial_storage_usage = env::storage_usage();
 			let owner_id = env::predecessor_account_id();
-			assert_eq!(owner_id.clone(), self.tokens().owner_id, "Unauthorized");
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
 
 			if let Some(base_uri) = base_uri {
 				let metadata = self.metadata.get();
 				if let Some(mut metadata) = metadata {
+					let old_metadata = metadata.clone();
 					metadata.base_uri = Some(base_uri);
 					self.metadata.set(&metadata);
+					log_contract_metadata_update(&old_metadata, &metadata);
 				}
 			}
-			let amt_to_refund = if env::storage_usage() > initial_storage_usage { env::storage_usage() - initial_storage_usage } else { initial_storage_usage - env::storage_usage() };
-			refund_deposit(amt_to_refund);
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Set (or clear, if `None`) contract metadata `extra`: a JSON string with collection-level info beyond NEP-177, e.g. a long
+		/// description & social links. Logs a `contract_metadata_update` event if it changed (see `log_contract_metadata_update`)
+		#[payable]
+		pub fn patch_contract_metadata_extra(
+				&mut self,
+				extra: Option<String>,
+		) -> NFTContractMetadata {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+			if let Some(extra) = &extra {
+				assert_valid_json(extra);
+			}
+
+			let old_metadata = self.internal_contract_metadata();
+			self.contract_metadata_extra = extra;
+			let metadata = self.internal_contract_metadata();
+			log_contract_metadata_update(&old_metadata, &metadata);
+
+			refund_or_reward_storage(initial_storage_usage);
+			metadata
+		}
+
+		/// Update delimiters used in TokenMetadata.title returned for individual tokens e.g. "Title — 2/10" (`None` leaves a delimiter unchanged)
+		#[payable]
+		pub fn patch_type_format(
+				&mut self,
+				title_delimeter: Option<String>,
+				edition_delimeter: Option<String>,
+		) {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+			if let Some(title_delimeter) = title_delimeter {
+				self.title_delimeter = title_delimeter;
+			}
+			if let Some(edition_delimeter) = edition_delimeter {
+				self.edition_delimeter = edition_delimeter;
+			}
+
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Update contract metadata fields individually (`None` leaves a field unchanged). `spec` cannot be changed.
+		/// `reference` & `reference_hash` are updated together if either is provided.
+		/// Logs a `contract_metadata_update` event listing changed fields, if any (see `log_contract_metadata_update`)
+		#[payable]
+		pub fn patch_contract_metadata(
+				&mut self,
+				name: Option<String>,
+				symbol: Option<String>,
+				icon: Option<String>,
+				reference: Option<String>,
+				reference_hash: Option<Base64VecU8>,
+		) -> NFTContractMetadata {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+			let mut metadata = self.internal_contract_metadata();
+			let old_metadata = metadata.clone();
+			if let Some(name) = name {
+				metadata.name = name;
+			}
+			if let Some(symbol) = symbol {
+				metadata.symbol = symbol;
+			}
+			if icon.is_some() {
+				metadata.icon = icon;
+			}
+			if reference.is_some() || reference_hash.is_some() {
+				metadata.reference = reference;
+				metadata.reference_hash = reference_hash;
+			}
+			metadata.assert_valid();
+			self.metadata.set(&metadata);
+			log_contract_metadata_update(&old_metadata, &metadata);
+
+			refund_or_reward_storage(initial_storage_usage);
+			metadata
+		}
+
+		/// Migrate up to `limit` token types from `token_type_by_id_v1` into `token_type_by_id`, removing them from `token_type_by_id_v1`.
+		/// Can be called repeatedly to migrate incrementally within gas limits. Returns number of token types migrated
+		pub fn migrate_token_types(&mut self, limit: u64) -> u64 {
+			assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+			let token_type_ids: Vec<TokenTypeId> = self.token_type_by_id_v1.keys().take(limit as usize).collect();
+			for token_type_id in token_type_ids.iter() {
+				self.internal_migrate_token_type(*token_type_id);
+			}
+			token_type_ids.len() as u64
+		}
+
+		/// Update `mint_fee` (paid to contract owner on each `nft_public_mint`). `None` removes the fee
+		#[payable]
+		pub fn patch_mint_fee(
+				&mut self,
+				mint_fee: Option<U128>,
+		) {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+			self.mint_fee = mint_fee;
+
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Get `mint_fee` (paid to contract owner on each `nft_public_mint`)
+		pub fn get_mint_fee(&self) -> Option<U128> {
+			self.mint_fee
+		}
+
+		/// Update `default_royalty` (used by `nft_create_type` when an empty royalty is provided). Empty map removes the default
+		#[payable]
+		pub fn patch_default_royalty(
+				&mut self,
+				default_royalty: HashMap<AccountId, u32>,
+		) {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+			assert_valid_royalty(&default_royalty);
+
+			self.default_royalty = default_royalty;
+
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Get `default_royalty` (used by `nft_create_type` when an empty royalty is provided)
+		pub fn get_default_royalty(&self) -> HashMap<AccountId, u32> {
+			self.default_royalty.clone()
+		}
+
+		/// Update `min_royalty` (minimum royalty every type must pay each of its accounts, enforced when a type's royalty is set).
+		/// Existing types are not affected. Empty map removes the minimum
+		#[payable]
+		pub fn patch_min_royalty(
+				&mut self,
+				min_royalty: HashMap<AccountId, u32>,
+		) {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+			assert_valid_royalty(&min_royalty);
+
+			self.min_royalty = min_royalty;
+
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Get `min_royalty` (minimum royalty every type must pay each of its accounts)
+		pub fn get_min_royalty(&self) -> HashMap<AccountId, u32> {
+			self.min_royalty.clone()
+		}
+
+		/// Update `max_copies_per_type` (max `copies` of a type enforced by `nft_create_type`). `None` removes the limit.
+		/// Existing types are not affected
+		#[payable]
+		pub fn patch_max_copies_per_type(
+				&mut self,
+				max_copies_per_type: Option<U64>,
+		) {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+			self.max_copies_per_type = max_copies_per_type.map(u64::from);
+
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Get `max_copies_per_type` (max `copies` of a type enforced by `nft_create_type`)
+		pub fn get_max_copies_per_type(&self) -> Option<U64> {
+			self.max_copies_per_type.map(U64)
+		}
+
+		/// Get total storage (in bytes) currently used by the contract account
+		pub fn contract_storage_usage(&self) -> U64 {
+			U64(env::storage_usage())
+		}
+
+		/// Get cost (in yoctoⓃ) of storage currently used by the contract account, i.e. the balance locked for storage staking
+		pub fn contract_storage_cost(&self) -> U128 {
+			U128(u128::from(env::storage_usage()) * env::storage_byte_cost())
+		}
+
+		/// Estimate storage cost (in yoctoⓃ) of creating a type with `metadata` & `assets` via `nft_create_type` (without `mint_all_to`),
+		/// i.e. the deposit it will keep. The type is built by `TokenType::new` as on creation, assuming:
+		/// * it is assigned the next type ID (`token_type_by_id.len() + 1`), as by `nft_create_type`
+		/// * the contract's `default_royalty`, with the first asset as cover
+		/// * no `price`, market or holdings cap (each of which would add a few bytes)
+		///
+		/// Record sizes follow the collections' storage layouts (see `storage_record_bytes`), so must be kept in step with
+		/// the collections written by `nft_create_type`
+		pub fn estimate_create_type_cost(&self, metadata: TokenTypeMetadata, mut assets: TokenTypeAssets) -> U128 {
+			let prefix_len = |storage_key: StorageKey| storage_key.try_to_vec().unwrap().len();
+
+			let title = metadata.title.clone().unwrap_or_else(|| env::panic_str(ERR_TITLE_REQUIRED));
+			require!(!assets.is_empty(), ERR_ASSETS_EMPTY);
+			assert_valid_assets(&assets);
+			// hashes are stored separately, as by `internal_store_asset_hashes`
+			let hashes: HashMap<String, String> = assets.iter_mut()
+				.filter(|asset_detail| asset_detail.len() == 4)
+				.map(|asset_detail| {
+					let hash = asset_detail.pop().unwrap();
+					(asset_detail[0].clone(), hash.to_lowercase())
+				})
+				.collect();
+
+			let token_type_id = self.token_type_by_id.len() + 1;
+			let token_type = TokenType::new(
+				token_type_id,
+				metadata,
+				self.tokens().owner_id.clone(),
+				self.default_royalty.clone(),
+				assets.len() as u64,
+				assets[0][0].clone(),
+			);
+			let token_type_len = VersionedTokenType::Current(token_type).try_to_vec().unwrap().len();
+
+			// `token_type_by_title` (LookupMap): keyed by prefix + title, value is the type ID
+			let mut bytes = storage_record_bytes(prefix_len(StorageKey::TokenTypeByTitle) + title.try_to_vec().unwrap().len(), 8);
+			// `token_type_by_id` (UnorderedMap): index -> key, index -> value & key -> index records, each keyed by prefix + 1 byte + 8 bytes
+			let by_id_key_len = prefix_len(StorageKey::TokenTypeById2) + 1 + 8;
+			bytes += storage_record_bytes(by_id_key_len, 8) + storage_record_bytes(by_id_key_len, 8) + storage_record_bytes(by_id_key_len, token_type_len);
+			// `token_type_assets_by_id` & `asset_hashes_by_type` (LookupMaps): keyed by prefix + type ID
+			bytes += storage_record_bytes(prefix_len(StorageKey::TokenTypeAssetsById) + 8, assets.try_to_vec().unwrap().len());
+			if !hashes.is_empty() {
+				bytes += storage_record_bytes(prefix_len(StorageKey::AssetHashesByType) + 8, hashes.try_to_vec().unwrap().len());
+			}
+
+			U128(Balance::from(bytes) * env::storage_byte_cost())
+		}
+
+		/// Withdraw `amount` of the contract's free balance (e.g. overpaid storage or NEAR sent by mistake) to `to`. Owner only.
+		/// Free balance excludes the balance locked for storage staking and accounts' storage deposits (see `storage_deposit`)
+		#[payable]
+		pub fn withdraw_near(&mut self, amount: U128, to: AccountId) -> Promise {
+			assert_one_yocto();
+			assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+			let locked_balance = u128::from(env::storage_usage()) * env::storage_byte_cost() + self.storage_deposits_total;
+			let free_balance = env::account_balance().saturating_sub(locked_balance);
+			let amount = u128::from(amount);
+			require!(amount <= free_balance, format!("Cannot withdraw more than free balance of {} yoctoNEAR", free_balance));
+			Promise::new(to).transfer(amount)
+		}
+
+		/// Update `max_batch_size` (max number of receivers accepted by `nft_batch_mint_type`). Must be greater than 0
+		#[payable]
+		pub fn patch_max_batch_size(
+				&mut self,
+				max_batch_size: U64,
+		) {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+			require!(max_batch_size.0 > 0, "max_batch_size must be greater than 0");
+
+			self.max_batch_size = max_batch_size.0;
+
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Get `max_batch_size` (max number of receivers accepted by `nft_batch_mint_type`)
+		pub fn get_max_batch_size(&self) -> U64 {
+			U64(self.max_batch_size)
+		}
+
+		/// Update `require_receiver_registration`. If true, transfers (incl. `nft_transfer_call` & payouts) panic unless the receiver
+		/// is registered via `storage_deposit`, e.g. for markets settling in FTs that require registration. Mints are not affected
+		#[payable]
+		pub fn patch_require_receiver_registration(
+				&mut self,
+				require_receiver_registration: bool,
+		) {
+			let initial_storage_usage = env::storage_usage();
+			let owner_id = env::predecessor_account_id();
+			assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+			self.require_receiver_registration = require_receiver_registration;
+
+			refund_or_reward_storage(initial_storage_usage);
+		}
+
+		/// Get `require_receiver_registration` (whether transfer receivers must be registered via `storage_deposit`)
+		pub fn get_require_receiver_registration(&self) -> bool {
+			self.require_receiver_registration
 		}
 
 }
\ No newline at end of file
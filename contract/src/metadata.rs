@@ -54,9 +54,25 @@ pub struct NFTContractMetadata {
     pub base_uri: Option<String>, // Centralized gateway known to have reliable access to decentralized storage assets referenced by `reference` or `media` URLs
     pub reference: Option<String>, // URL to a JSON file with more info
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+    /// Not part of NEP-177: JSON string with collection-level info e.g. `{"description":"...","links":{"twitter":"..."}}`.
+    /// Optional & omitted-if-missing in JSON, so existing NEP-177 clients are unaffected. Skipped by Borsh, as metadata stored before
+    /// this field was added wouldn't deserialize; stored in `Contract.contract_metadata_extra` instead
+    #[borsh_skip]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<String>,
 }
 
-/// Metadata for a type/series.
+/// OLD Metadata for a type/series (stored on all token types prior to addition of `reference` & `reference_hash`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenTypeMetadataV1 {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub copies: Option<u64>,
+}
+
+/// CURRENT Metadata for a type/series.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TokenTypeMetadata {
@@ -68,14 +84,42 @@ pub struct TokenTypeMetadata {
     pub media: Option<String>,
     /// total number of copies for this NFT (minted + to-be-minted)
     pub copies: Option<u64>,
+    // NEW FIELDS
+    /// URL to an off-chain JSON manifest for this type/series
+    pub reference: Option<String>,
+    /// Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+    pub reference_hash: Option<Base64VecU8>,
 }
 
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub enum VersionedTokenTypeMetadata {
+    V1(TokenTypeMetadataV1),
     Current(TokenTypeMetadata),
 }
 
+impl From<TokenTypeMetadataV1> for TokenTypeMetadata {
+    fn from(v1: TokenTypeMetadataV1) -> Self {
+        TokenTypeMetadata {
+            title: v1.title,
+            description: v1.description,
+            media: v1.media,
+            copies: v1.copies,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+}
+
+impl From<VersionedTokenTypeMetadata> for TokenTypeMetadata {
+    fn from(metadata: VersionedTokenTypeMetadata) -> Self {
+        match metadata {
+            VersionedTokenTypeMetadata::Current(current) => current,
+            VersionedTokenTypeMetadata::V1(v1) => TokenTypeMetadata::from(v1),
+        }
+    }
+}
+
 /// OLD Metadata on the individual token level
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -86,6 +130,17 @@ pub struct TokenMetadataV1 {
     pub copies: Option<u64>, // number of copies of this set of metadata in existence when token was minted.
 }
 
+/// OLD Metadata on the individual token level (stored on all NFTs prior to addition of `updated_at`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadataV2 {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub copies: Option<u64>,
+    pub extra: Option<String>,
+}
+
 /// CURRENT Metadata on the individual token level
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -101,27 +156,42 @@ pub struct TokenMetadata {
     // NEW FIELDS
     /// When stored in `token_metadata_by_id`, this is filename of extra asset (e.g. json) on IPFS. When returned as metadata on token enumeration methods, it is {cid}/{filename}, which can be appended to the contract's base url to create a full `extra` url
     pub extra: Option<String>,
-    // TODO: add `updatedAt`? other fields?
+    /// Block timestamp (nanoseconds) at which this NFT's stored metadata was last written, i.e. on mint & when its media is patched
+    /// (`patch_media_and_assets_for_token_type`), so clients caching `nft_token` can detect changes. `None` for NFTs minted before it was added
+    pub updated_at: Option<U64>,
 }
 
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub enum VersionedTokenMetadata {
+    V2(TokenMetadataV2),
     Current(TokenMetadata),
 }
 
+impl From<TokenMetadataV2> for TokenMetadata {
+    fn from(v2: TokenMetadataV2) -> Self {
+        TokenMetadata {
+            title: v2.title,
+            description: v2.description,
+            media: v2.media,
+            copies: v2.copies,
+            extra: v2.extra,
+            updated_at: None,
+        }
+    }
+}
+
 impl From<VersionedTokenMetadata> for TokenMetadata {
     fn from(metadata: VersionedTokenMetadata) -> Self {
         match metadata {
             VersionedTokenMetadata::Current(current) => current,
+            VersionedTokenMetadata::V2(v2) => TokenMetadata::from(v2),
         }
     }
 }
 
 pub fn versioned_token_metadata_to_token_metadata(versioned_metadata: VersionedTokenMetadata) -> TokenMetadata {
-    match versioned_metadata {
-        VersionedTokenMetadata::Current(current) => current,
-    }
+    TokenMetadata::from(versioned_metadata)
 }
 
 /// Offers details on the contract-level metadata.
@@ -133,20 +203,44 @@ pub trait NonFungibleTokenMetadataProvider {
 #[near_bindgen]
 impl NonFungibleTokenMetadataProvider for Contract {
     fn nft_metadata(&self) -> NFTContractMetadata {
-        self.metadata.get().unwrap()
+        self.internal_contract_metadata()
     }
 
     #[payable]
     fn nft_update_contract_metadata(&mut self, new_metadata: NFTContractMetadata) -> NFTContractMetadata {
         let initial_storage_usage = env::storage_usage();
         let owner_id = env::predecessor_account_id();
-        assert_eq!(owner_id.clone(), self.tokens().owner_id, "Unauthorized");
+        assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+        if let Some(extra) = &new_metadata.extra {
+            assert_valid_json(extra);
+        }
 
         self.metadata.set(&new_metadata);
+        self.contract_metadata_extra = new_metadata.extra;
+
+        refund_or_reward_storage(initial_storage_usage);
+        self.internal_contract_metadata()
+    }
+}
 
-        let amt_to_refund = if env::storage_usage() > initial_storage_usage { env::storage_usage() - initial_storage_usage } else { initial_storage_usage - env::storage_usage() };
-        refund_deposit(amt_to_refund);
-        self.metadata.get().unwrap()
+impl Contract {
+    /// Stored contract metadata, with `extra` (stored separately) filled in
+    pub(crate) fn internal_contract_metadata(&self) -> NFTContractMetadata {
+        let mut metadata = self.metadata.get().unwrap();
+        metadata.extra = self.contract_metadata_extra.clone();
+        metadata
+    }
+}
+
+impl TokenTypeMetadata {
+    pub fn assert_valid(&self) {
+        require!(
+            self.reference.is_some() == self.reference_hash.is_some(),
+            "Reference and reference hash must be present"
+        );
+        if let Some(reference_hash) = &self.reference_hash {
+            require!(reference_hash.0.len() == 32, "Hash has to be 32 bytes");
+        }
     }
 }
 
@@ -160,5 +254,16 @@ impl NFTContractMetadata {
         if let Some(reference_hash) = &self.reference_hash {
             require!(reference_hash.0.len() == 32, "Hash has to be 32 bytes");
         }
+        if let Some(extra) = &self.extra {
+            assert_valid_json(extra);
+        }
     }
+}
+
+/// Panic if `extra` (contract metadata `extra`) isn't valid JSON
+pub(crate) fn assert_valid_json(extra: &str) {
+    require!(
+        near_sdk::serde_json::from_str::<near_sdk::serde_json::Value>(extra).is_ok(),
+        "extra must be a valid JSON string"
+    );
 }
\ No newline at end of file
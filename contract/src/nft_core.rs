@@ -3,12 +3,15 @@ use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LookupMap, TreeMap, UnorderedSet};
 use near_sdk::{
     assert_one_yocto, env, ext_contract, log, require, AccountId, Balance,
-    Gas, IntoStorageKey, PromiseOrValue, PromiseResult, StorageUsage,
+    Gas, IntoStorageKey, Promise, PromiseOrValue, PromiseResult, StorageUsage,
 };
 use std::collections::HashMap;
 
+/// gas reserved for the `nft_resolve_transfer` callback of `nft_transfer_call`
 const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
-const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.0);
+/// gas used by `nft_transfer_call` itself (transfer & scheduling the receiver call and callback). All prepaid gas beyond this and
+/// `GAS_FOR_RESOLVE_TRANSFER` is forwarded to the receiver's `nft_on_transfer`
+const GAS_FOR_NFT_TRANSFER_CALL_OVERHEAD: Gas = Gas(25_000_000_000_000);
 
 const NO_DEPOSIT: Balance = 0;
 
@@ -31,8 +34,8 @@ pub trait NonFungibleTokenCore {
   ///   see https://nomicon.io/Standards/NonFungibleToken/ApprovalManagement.html
   /// * If using Approval Management, contract MUST nullify approved accounts on
   ///   successful transfer.
-  /// * TODO: needed? Both accounts must be registered with the contract for transfer to
-  ///   succeed. See see https://nomicon.io/Standards/StorageManagement.html
+  /// * If the contract's `require_receiver_registration` is set, `receiver_id` must be registered
+  ///   with the contract (via `storage_deposit`) for transfer to succeed. See https://nomicon.io/Standards/StorageManagement.html
   ///
   /// Arguments:
   /// * `receiver_id`: the valid NEAR account receiving the token
@@ -282,6 +285,7 @@ impl NonFungibleToken {
                 // asset_id: Some(String::from("1")),
                 // filetype: Some(String::from("jpg")),
                 extra: Some(String::from("1.json")),
+                updated_at: Some(U64(0)),
             };
             token_metadata_by_id.insert(
                 &tmp_token_id,
@@ -351,7 +355,7 @@ impl NonFungibleToken {
 			if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
 			// owner_tokens should always exist, so call `unwrap` without guard
 			let mut owner_tokens = tokens_per_owner.get(from).unwrap_or_else(|| {
-					env::panic_str("Unable to access tokens per owner in unguarded call.")
+					env::panic_str(ERR_TOKENS_PER_OWNER_UNGUARDED)
 			});
 			owner_tokens.remove(token_id);
 			if owner_tokens.is_empty() {
@@ -381,7 +385,7 @@ impl NonFungibleToken {
         approval_id: Option<u64>,
         memo: Option<String>,
         ) -> (AccountId, Option<HashMap<AccountId, u64>>) {
-        let owner_id = self.owner_by_id.get(token_id).unwrap_or_else(|| env::panic_str("Token not found"));
+        let owner_id = self.owner_by_id.get(token_id).unwrap_or_else(|| env::panic_str(ERR_TOKEN_NOT_FOUND));
 
         // clear approvals, if using Approval Management extension
         // this will be rolled back by a panic if sending fails
@@ -390,14 +394,14 @@ impl NonFungibleToken {
         // check if authorized
         let sender_id = if sender_id != &owner_id {
             // if approval extension is NOT being used, or if token has no approved accounts
-            let app_acc_ids = approved_account_ids.as_ref().unwrap_or_else(|| env::panic_str("Unauthorized"));
+            let app_acc_ids = approved_account_ids.as_ref().unwrap_or_else(|| env::panic_str(ERR_UNAUTHORIZED));
 
             // Approval extension is being used; get approval_id for sender.
             let actual_approval_id = app_acc_ids.get(sender_id);
 
             // Panic if sender not approved at all
             if actual_approval_id.is_none() {
-                env::panic_str("Sender not approved");
+                env::panic_str(ERR_SENDER_NOT_APPROVED);
             }
 
             // If approval_id included, check that it matches
@@ -413,39 +417,30 @@ impl NonFungibleToken {
             None
         };
 
-        require!(&owner_id != receiver_id, "Current and next owner must differ");
+        require!(&owner_id != receiver_id, ERR_SAME_OWNER);
 
         self.internal_transfer_unguarded(token_id, &owner_id, receiver_id);
 
-        // NonFungibleToken::emit_transfer(&owner_id, receiver_id, token_id, sender_id, memo);
-        env::log_str(format!("{}{}", EVENT_JSON, json!({
-            "standard": "nep171",
-            "version": "1.0.0",
-            "event": "nft_transfer",
-            "data": [
-                {
-                    "old_owner_id": owner_id, "new_owner_id": receiver_id, "token_ids": [token_id]
-                }
-            ]
-        })).as_ref());
+        emit_nft_transfer(&owner_id, receiver_id, &[token_id.clone()], sender_id, memo.as_deref());
 
         // return previous owner & approvals
         (owner_id, approved_account_ids)
     }
 
     /// Mint a new token without checking whether the caller id is equal to the `owner_id`
+    ///
+    /// Does not charge for storage; callers are responsible for covering the storage used
     pub fn internal_mint(
         &mut self,
         token_id: TokenId,
         token_owner_id: AccountId,
         token_metadata: Option<VersionedTokenMetadata>,
     ) -> Token {
-        let initial_storage_usage = env::storage_usage();
         if self.token_metadata_by_id.is_some() && token_metadata.is_none() {
-            env::panic_str("Must provide metadata");
+            env::panic_str(ERR_MISSING_METADATA);
         }
         if self.owner_by_id.get(&token_id).is_some() {
-            env::panic_str("token_id must be unique");
+            env::panic_str(ERR_TOKEN_ID_NOT_UNIQUE);
         }
 
         let owner_id: AccountId = token_owner_id;
@@ -475,13 +470,42 @@ impl NonFungibleToken {
         let approved_account_ids =
             if self.approvals_by_id.is_some() { Some(HashMap::new()) } else { None };
 
-        // Return any extra attached deposit not used for storage
-        refund_deposit(env::storage_usage() - initial_storage_usage);
-
         let token = Token { token_id, owner_id, metadata: Some(TokenMetadata::from(token_metadata.unwrap())), approved_account_ids };
 
         token
     }
+
+    /// Remove a token without checking whether the caller id is equal to the token owner
+    ///
+    /// Approvals are cleared but not refunded; callers are responsible for refunding approvals storage to the token owner.
+    /// Returns token owner, token metadata & cleared approvals
+    pub fn internal_remove(
+        &mut self,
+        #[allow(clippy::ptr_arg)] token_id: &TokenId,
+    ) -> (AccountId, Option<VersionedTokenMetadata>, Option<HashMap<AccountId, u64>>) {
+        let owner_id = self.owner_by_id.remove(token_id).unwrap_or_else(|| env::panic_str(ERR_TOKEN_NOT_FOUND));
+
+        let token_metadata = self.token_metadata_by_id.as_mut().and_then(|by_id| by_id.remove(token_id));
+
+        if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
+            let mut owner_tokens = tokens_per_owner.get(&owner_id).unwrap_or_else(|| {
+                env::panic_str(ERR_TOKENS_PER_OWNER_UNGUARDED)
+            });
+            owner_tokens.remove(token_id);
+            if owner_tokens.is_empty() {
+                tokens_per_owner.remove(&owner_id);
+            } else {
+                tokens_per_owner.insert(&owner_id, &owner_tokens);
+            }
+        }
+
+        let approved_account_ids = self.approvals_by_id.as_mut().and_then(|by_id| by_id.remove(token_id));
+        if let Some(next_approval_id_by_id) = &mut self.next_approval_id_by_id {
+            next_approval_id_by_id.remove(token_id);
+        }
+
+        (owner_id, token_metadata, approved_account_ids)
+    }
 }
 
 #[near_bindgen]
@@ -497,7 +521,7 @@ impl NonFungibleTokenCore for Contract {
 	    ) {
 		assert_one_yocto();
 		let sender_id = env::predecessor_account_id();
-		self.tokens_mut().internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+		self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo, false);
 	}
 
     #[payable]
@@ -510,8 +534,13 @@ impl NonFungibleTokenCore for Contract {
         msg: String,
         ) -> PromiseOrValue<bool> {
         assert_one_yocto();
+        let reserved_gas = GAS_FOR_RESOLVE_TRANSFER.0 + GAS_FOR_NFT_TRANSFER_CALL_OVERHEAD.0;
+        let receiver_gas = env::prepaid_gas().0.checked_sub(reserved_gas).map(Gas).unwrap_or_else(|| {
+            env::panic_str(&format!("Must attach more than {} gas to nft_transfer_call", reserved_gas))
+        });
         let sender_id = env::predecessor_account_id();
-        let (old_owner, old_approvals) = self.tokens_mut().internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+        // any lock is kept until `nft_resolve_transfer`, so it still applies if the NFT is returned
+        let (old_owner, old_approvals) = self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo, true);
         // Initiating receiver's call and the callback
         ext_receiver::nft_on_transfer(
             sender_id,
@@ -520,7 +549,7 @@ impl NonFungibleTokenCore for Contract {
             msg,
             receiver_id.clone(),
             NO_DEPOSIT,
-            env::prepaid_gas() - GAS_FOR_FT_TRANSFER_CALL,
+            receiver_gas,
         )
         .then(ext_self::nft_resolve_transfer(
             old_owner,
@@ -543,67 +572,438 @@ impl NonFungibleTokenCore for Contract {
             .and_then(|by_id| by_id.get(&token_id).or_else(|| Some(HashMap::new())));
 
 		// CUSTOM (switch metadata for the token_type metadata)
-		let mut token_id_iter = token_id.split(TOKEN_DELIMETER);
-		let token_type_id = token_id_iter.next().unwrap().parse().unwrap();
-		// make edition titles nice for showing in wallet
-        let versioned_token_type = self.token_type_by_id.get(&token_type_id);
-        let token_type = versioned_token_type_to_token_type(versioned_token_type.unwrap());
+		// token_ids not of the form `{token_type_id}:{edition}` (e.g. legacy ids) can't be resolved to a type, so are treated as missing
+		let (token_type_id, edition) = token_id.split_once(TOKEN_DELIMETER)?;
+		let token_type_id: TokenTypeId = token_type_id.parse().ok()?;
+        let versioned_token_type = self.token_type_by_id.get(&token_type_id)?;
+        let token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+        let token_metadata_versioned = tokens.token_metadata_by_id.as_ref()?.get(&token_id)?;
+        let token_metadata = TokenMetadata::from(token_metadata_versioned);
+
+        let final_metadata = self.internal_assemble_token_metadata(token_type, edition, &token_metadata);
+		
+		// CUSTOM
+		// implement this if you need to combine individual token metadata
+		// e.g. metadata.extra with TokenType.metadata.extra and return something unique
+		// let token_metadata = self.tokens.token_metadata_by_id.get(&token_id)?;
+		// metadata.extra = token_metadata.extra;
+        let token = Token {
+            token_id,
+            owner_id,
+            metadata: Some(final_metadata),
+            approved_account_ids,
+        };
+        Some(token)
+	}
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Get fully-resolved media URL of a token: contract `base_uri` (if set) + `media` as returned by `nft_token`
+    pub fn nft_token_media_url(&self, token_id: TokenId) -> Option<String> {
+        let media = self.nft_token(token_id)?.metadata?.media?;
+        Some(self.internal_with_base_uri(media))
+    }
+
+    /// Get fully-resolved extra (e.g. off-chain json) URL of a token: contract `base_uri` (if set) + `extra` as returned by `nft_token`
+    pub fn nft_token_extra_url(&self, token_id: TokenId) -> Option<String> {
+        let extra = self.nft_token(token_id)?.metadata?.extra?;
+        Some(self.internal_with_base_uri(extra))
+    }
+
+    /// Get a token as returned by `nft_token`, along with the royalty of its type (saves a separate `nft_get_type` call)
+    pub fn nft_token_with_royalty(&self, token_id: TokenId) -> Option<(Token, HashMap<AccountId, u32>)> {
+        let token = self.nft_token(token_id.clone())?;
+        let versioned_token_type = self.token_type_by_id.get(&token_type_id_from_token_id(&token_id))?;
+        let token_type = versioned_token_type_to_token_type(versioned_token_type);
+        Some((token, token_type.royalty))
+    }
+
+    /// Preview metadata of a (possibly not yet minted) edition of a type, assembled as by `nft_token`. `edition` is clamped to `[1, copies]`.
+    /// The type's `cover_asset` stands in for the (randomly selected) asset
+    pub fn nft_preview_token(&self, token_type_title: TokenTypeTitle, edition: u64) -> TokenMetadata {
+        let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+        let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+        let token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+        let edition = edition.min(token_type.metadata.copies.unwrap_or(u64::MAX)).max(1);
+        let extra = token_type.cover_asset.as_ref().and_then(|cover_asset| {
+            self.token_type_assets_by_id.get(&token_type_id)?
+                .into_iter()
+                .find(|asset_detail| &asset_detail[0] == cover_asset)
+                .map(|asset_detail| asset_detail[2].clone())
+                .filter(|extra| !extra.is_empty())
+        });
+        let token_metadata = TokenMetadata {
+            title: None,
+            description: None,
+            media: token_type.cover_asset.clone(),
+            copies: None,
+            extra,
+            updated_at: None,
+        };
+        self.internal_assemble_token_metadata(token_type, &edition.to_string(), &token_metadata)
+    }
+
+    /// Get token metadata as stored (unassembled), e.g. `media` & `extra` are filenames within the type's `media` directory
+    /// rather than the resolved paths returned by `nft_token`
+    pub fn nft_token_raw_metadata(&self, token_id: TokenId) -> Option<TokenMetadata> {
+        let token_metadata_by_id = self.tokens().token_metadata_by_id.as_ref()?;
+        token_metadata_by_id.get(&token_id).map(TokenMetadata::from)
+    }
+
+    /// Whether an NFT was minted before types had assets (pre-generative upgrade), i.e. its stored metadata has no asset filename
+    /// (`media`), so `nft_token` resolves its media to the type's `media` itself. Panics if the NFT doesn't exist
+    pub fn nft_is_legacy_token(&self, token_id: TokenId) -> bool {
+        let token_metadata = self.nft_token_raw_metadata(token_id).unwrap_or_else(|| env::panic_str(ERR_TOKEN_NOT_FOUND));
+        token_metadata.media.is_none()
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Burn any NFT regardless of its owner (e.g. to retire test mints). Owner only.
+    ///
+    /// Storage released by the NFT is refunded to the contract owner (approvals storage is refunded to the token owner, who paid for it).
+    /// Unlike `nft_burn`, the contract owner needn't own or be approved for the NFT
+    #[payable]
+    pub fn nft_admin_burn(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        let contract_owner_id = self.tokens().owner_id.clone();
+        assert_eq!(env::predecessor_account_id(), contract_owner_id, "{}", ERR_UNAUTHORIZED);
+
+        let initial_storage_usage = env::storage_usage();
+        let (owner_id, approved_account_ids, lock_storage) = self.internal_burn(&token_id);
+
+        // lock storage has already been refunded to the token owner, who paid for it
+        let mut storage_released = initial_storage_usage.saturating_sub(env::storage_usage()).saturating_sub(lock_storage);
+        if let Some(approved_account_ids) = approved_account_ids {
+            let approvals_storage: u64 = approved_account_ids.keys().map(bytes_for_approved_account_id).sum();
+            storage_released = storage_released.saturating_sub(approvals_storage);
+            refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+        }
+        if storage_released > 0 {
+            Promise::new(contract_owner_id.clone()).transfer(Balance::from(storage_released) * env::storage_byte_cost());
+        }
+
+        env::log_str(format!("{}{}", EVENT_JSON, json!({
+            "standard": "nep171",
+            "version": "1.0.0",
+            "event": "nft_burn",
+            "data": [
+                {
+                    "owner_id": owner_id,
+                    "authorized_id": contract_owner_id,
+                    "token_ids": [token_id]
+                }
+            ]
+        })).as_ref());
+    }
+
+    /// Burn an NFT. The caller must own or be approved for it (and be its locker, if locked). Requires 1 yoctoⓃ attached
+    ///
+    /// Storage released by the NFT is refunded to the contract owner, who paid for it on mint (approvals storage is refunded to the
+    /// token owner). Logs an `nft_burn` event
+    #[payable]
+    pub fn nft_burn(&mut self, token_id: TokenId) {
+        self.nft_burn_batch(vec![token_id]);
+    }
+
+    /// Burn a batch of NFTs (at most `MAX_TOKENS_PER_BURN`), e.g. to retire a failed test series. Authorized per NFT as by `nft_burn`.
+    /// Requires 1 yoctoⓃ attached
+    ///
+    /// Storage released by each NFT is refunded to the contract owner, who paid for it on mint (approvals storage is refunded to the
+    /// token owner). Logs a single `nft_burn` event with an entry per previous owner
+    #[payable]
+    pub fn nft_burn_batch(&mut self, token_ids: Vec<TokenId>) {
+        assert_one_yocto();
+        require!(!token_ids.is_empty(), ERR_TOKEN_IDS_EMPTY);
+        require!(
+            token_ids.len() <= MAX_TOKENS_PER_BURN,
+            format!("Cannot burn more than {} tokens at once", MAX_TOKENS_PER_BURN)
+        );
+        let sender_id = env::predecessor_account_id();
+
+        // (owner, token_ids) in order of first appearance, for the event
+        let mut token_ids_by_owner: Vec<(AccountId, Vec<TokenId>)> = Vec::new();
+        let mut storage_released: u64 = 0;
+        for token_id in token_ids {
+            let owner_id = self.tokens().owner_by_id.get(&token_id).unwrap_or_else(|| env::panic_str(&format!("{}: {}", ERR_TOKEN_NOT_FOUND, token_id)));
+            if sender_id != owner_id {
+                let approved = self.tokens().approvals_by_id.as_ref()
+                    .and_then(|by_id| by_id.get(&token_id))
+                    .map_or(false, |approved_account_ids| approved_account_ids.contains_key(&sender_id));
+                require!(approved, ERR_SENDER_NOT_APPROVED);
+            }
+            if let Some(locker_id) = self.locked_by_id.get(&token_id) {
+                require!(sender_id == locker_id, format!("{} {}", ERR_TOKEN_LOCKED_BY, locker_id));
+            }
+
+            let initial_storage_usage = env::storage_usage();
+            let (owner_id, approved_account_ids, lock_storage) = self.internal_burn(&token_id);
+            // lock storage has already been refunded to the token owner, who paid for it
+            let mut token_storage_released = initial_storage_usage.saturating_sub(env::storage_usage()).saturating_sub(lock_storage);
+            if let Some(approved_account_ids) = approved_account_ids {
+                // paid for by the token owner, so refunded to them
+                token_storage_released = token_storage_released.saturating_sub(approved_account_ids.keys().map(bytes_for_approved_account_id).sum());
+                refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+            }
+            storage_released += token_storage_released;
+            match token_ids_by_owner.iter_mut().find(|(id, _)| id == &owner_id) {
+                Some((_, owner_token_ids)) => owner_token_ids.push(token_id),
+                None => token_ids_by_owner.push((owner_id, vec![token_id])),
+            }
+        }
+
+        // token storage was paid by the contract owner on mint, so holders can't burn to withdraw it
+        if storage_released > 0 {
+            Promise::new(self.tokens().owner_id.clone()).transfer(Balance::from(storage_released) * env::storage_byte_cost());
+        }
+
+        let data: Vec<_> = token_ids_by_owner.into_iter().map(|(owner_id, token_ids)| {
+            if owner_id == sender_id {
+                json!({ "owner_id": owner_id, "token_ids": token_ids })
+            } else {
+                json!({ "owner_id": owner_id, "authorized_id": sender_id, "token_ids": token_ids })
+            }
+        }).collect();
+        env::log_str(format!("{}{}", EVENT_JSON, json!({
+            "standard": "nep171",
+            "version": "1.0.0",
+            "event": "nft_burn",
+            "data": data
+        })).as_ref());
+    }
+
+    /// Lock an NFT on behalf of `locker_id` (e.g. a marketplace holding an auction), so that only `locker_id` can transfer it
+    /// (which must also be approved for the NFT) until it is unlocked. Token owner only; caller pays for storage
+    #[payable]
+    pub fn nft_lock(&mut self, token_id: TokenId, locker_id: AccountId) {
+        let initial_storage_usage = env::storage_usage();
+        let owner_id = self.tokens().owner_by_id.get(&token_id).unwrap_or_else(|| env::panic_str(ERR_TOKEN_NOT_FOUND));
+        require!(env::predecessor_account_id() == owner_id, ERR_NOT_TOKEN_OWNER);
+        require!(locker_id != owner_id, ERR_OWNER_CANNOT_LOCK);
+        require!(self.locked_by_id.get(&token_id).is_none(), ERR_ALREADY_LOCKED);
+
+        self.locked_by_id.insert(&token_id, &locker_id);
+
+        refund_deposit(env::storage_usage() - initial_storage_usage);
+    }
+
+    /// Unlock a locked NFT. Callable by its locker or the contract owner (e.g. if the locker is no longer operating).
+    /// Storage is refunded to the token owner, who paid for it
+    #[payable]
+    pub fn nft_unlock(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        let locker_id = self.locked_by_id.get(&token_id).unwrap_or_else(|| env::panic_str(ERR_NOT_LOCKED));
+        let predecessor_account_id = env::predecessor_account_id();
+        require!(
+            predecessor_account_id == locker_id || predecessor_account_id == self.tokens().owner_id,
+            ERR_NOT_LOCKER
+        );
+        let owner_id = self.tokens().owner_by_id.get(&token_id).unwrap_or_else(|| env::panic_str(ERR_TOKEN_NOT_FOUND));
+
+        self.internal_remove_lock(&token_id, &owner_id);
+    }
+
+    /// Get the locker of an NFT (`None` if it isn't locked)
+    pub fn nft_locked_by(&self, token_id: TokenId) -> Option<AccountId> {
+        self.locked_by_id.get(&token_id)
+    }
+
+    /// Transfer an NFT referenced by its type/series title & edition number rather than token_id, as `nft_transfer`.
+    /// Panics if the edition was never minted
+    #[payable]
+    pub fn nft_transfer_by_edition(
+        &mut self,
+        receiver_id: AccountId,
+        token_type_title: TokenTypeTitle,
+        edition: u64,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+        let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+        let token_type = versioned_token_type_to_token_type(versioned_token_type);
+        // editions above `last_edition` are only minted if claimed; reserved editions below it were skipped
+        let minted = if self.reserved_editions.contains_key(&(token_type_id, edition)) {
+            false
+        } else {
+            edition <= token_type.last_edition || self.claimed_editions.contains(&(token_type_id, edition))
+        };
+        require!(edition >= 1 && minted, format!("Edition {} was never minted", edition));
+
+        let token_id = format!("{}{}{}", token_type_id, TOKEN_DELIMETER, edition);
+        let sender_id = env::predecessor_account_id();
+        self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo, false);
+    }
+}
+
+impl Contract {
+    /// Remove an NFT from its owner and its type, counting it as burned on the type (so its edition number isn't reissued).
+    ///
+    /// Performs no authorization checks or event logging, and no storage refunds other than of the NFT's lock (if any), which is refunded to
+    /// the owner. Returns previous token owner, cleared approvals & storage released by the lock
+    pub(crate) fn internal_burn(&mut self, #[allow(clippy::ptr_arg)] token_id: &TokenId) -> (AccountId, Option<HashMap<AccountId, u64>>, u64) {
+        let token_type_id = token_type_id_from_token_id(token_id);
+        let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+        let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+        let (owner_id, _, approved_account_ids) = self.tokens_mut().internal_remove(token_id);
+        let lock_storage = self.internal_remove_lock(token_id, &owner_id);
+
+        token_type.tokens.remove(token_id);
+        token_type.num_burned += 1;
+        self.num_burned += 1;
+        self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+        self.internal_decrement_holdings(token_type_id, &owner_id);
+
+        (owner_id, approved_account_ids, lock_storage)
+    }
+
+    /// Remove the lock of an NFT (if locked), refunding its storage to `owner_id`, who paid for it in `nft_lock`. Returns the storage
+    /// released, so callers refunding other released storage can exclude it
+    pub(crate) fn internal_remove_lock(&mut self, #[allow(clippy::ptr_arg)] token_id: &TokenId, owner_id: &AccountId) -> u64 {
+        let initial_storage_usage = env::storage_usage();
+        if self.locked_by_id.remove(token_id).is_none() {
+            return 0;
+        }
+        let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+        if storage_released > 0 {
+            Promise::new(owner_id.clone()).transfer(Balance::from(storage_released) * env::storage_byte_cost());
+        }
+        storage_released
+    }
+
+    /// Assemble NFT metadata for display (as returned by `nft_token`) from its type, edition number & stored token metadata
+    pub(crate) fn internal_assemble_token_metadata(&self, token_type: TokenType, edition: &str, token_metadata: &TokenMetadata) -> TokenMetadata {
+		// token-level title/description (if set at mint) override the type's
 		let mut final_metadata = TokenMetadata {
-            title: token_type.metadata.title,
-            description: token_type.metadata.description,
+            title: token_metadata.title.clone().or(token_type.metadata.title),
+            description: token_metadata.description.clone().or(token_type.metadata.description),
             media: token_type.metadata.media,
             copies: token_type.metadata.copies,
             extra: None,
+            updated_at: token_metadata.updated_at,
         };
 
-        let token_metadata_versioned = tokens.token_metadata_by_id.as_ref().unwrap().get(&token_id).unwrap();
-        let token_metadata = TokenMetadata::from(token_metadata_versioned);
-
+		// make edition titles nice for showing in wallet
         if let Some(copies) = final_metadata.copies {
-            // {TITLE}{TITLE_DELIMITER}{TOKEN_NUMBER}{EDITION_DELIMETER}{COPIES} e.g. "Lachlan's Serial NFT Project - 2/10"
+            // edition denominator is always the original copies, so titles don't change if copies are capped
+            let copies = token_type.original_copies.unwrap_or(copies);
+            // {TITLE}{title_delimeter}{TOKEN_NUMBER}{edition_delimeter}{COPIES} e.g. "Lachlan's Serial NFT Project - 2/10"
 			final_metadata.title = if token_type.asset_count == 1 {
                 Some(format!(
                     "{}{}{}{}{}",
                     final_metadata.title.unwrap(),
-                    TITLE_DELIMETER,
-                    token_id_iter.next().unwrap(),
-                    EDITION_DELIMETER,
+                    self.title_delimeter,
+                    edition,
+                    self.edition_delimeter,
                     copies
                 ))
             } else {
-                // {TITLE}{TITLE_DELIMITER}{FILENAME} e.g. "Lachlan's Generative NFT Project - #4537" (in this case, media would be stored on IPFS as #4537.png or #4537.mp4 etc)
+                // {TITLE}{title_delimeter}{FILENAME} e.g. "Lachlan's Generative NFT Project - #4537" (in this case, media would be stored on IPFS as #4537.png or #4537.mp4 etc)
                 Some(format!(
                     "{}{}{}",
                     final_metadata.title.unwrap(),
-                    TITLE_DELIMETER,
+                    self.title_delimeter,
                     token_metadata.media.clone().unwrap().split(FILE_DELIMETER).next().unwrap(),
                 ))
             }
 		}
         
-        let extra = &token_metadata.extra;
         let type_media = final_metadata.clone().media.unwrap();
-        
-        final_metadata.media = Some(format!("{}/{}", type_media.clone(), token_metadata.media.unwrap()));
+        let (media, extra) = Self::internal_resolve_token_media(&type_media, token_metadata);
+        final_metadata.media = Some(media);
+        final_metadata.extra = extra;
+        final_metadata
+    }
 
-        if extra.is_some() {
-            // media cid for this series (directory cid) + token_metadata.extra maps to a json asset on IPFS
-            final_metadata.extra = Some(format!("{}/{}", type_media.clone(), token_metadata.extra.unwrap()));
-        }
-		
-		// CUSTOM
-		// implement this if you need to combine individual token metadata
-		// e.g. metadata.extra with TokenType.metadata.extra and return something unique
-		// let token_metadata = self.tokens.token_metadata_by_id.get(&token_id)?;
-		// metadata.extra = token_metadata.extra;
-        let token = Token {
-            token_id,
-            owner_id,
-            metadata: Some(final_metadata),
-            approved_account_ids,
+    /// Resolve a token's `media` & `extra` filenames against its type's `media` (IPFS directory CID) e.g. "abcd1234/cat.jpg".
+    /// Tokens without a media filename of their own fall back to the type's `media` directly
+    pub(crate) fn internal_resolve_token_media(type_media: &str, token_metadata: &TokenMetadata) -> (String, Option<String>) {
+        let media = match &token_metadata.media {
+            Some(filename) => format!("{}/{}", type_media, filename),
+            None => type_media.to_string(),
         };
-        Some(token)
-	}
+        // media cid for this series (directory cid) + token_metadata.extra maps to a json asset on IPFS
+        let extra = token_metadata.extra.as_ref().map(|filename| format!("{}/{}", type_media, filename));
+        (media, extra)
+    }
+
+    /// Prefix `path` with contract `base_uri`, if set
+    pub(crate) fn internal_with_base_uri(&self, path: String) -> String {
+        match self.metadata.get().and_then(|metadata| metadata.base_uri) {
+            Some(base_uri) => format!("{}/{}", base_uri.trim_end_matches('/'), path),
+            None => path,
+        }
+    }
+
+    /// Transfer via `NonFungibleToken::internal_transfer`, additionally enforcing & tracking type-level holdings (and receiver
+    /// registration, if `require_receiver_registration` is set).
+    ///
+    /// A locked NFT can only be transferred by its locker. The lock is removed (refunding its storage to the previous owner) unless
+    /// `keep_lock`, in which case the caller is responsible for removing it
+    pub(crate) fn internal_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        #[allow(clippy::ptr_arg)] token_id: &TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        keep_lock: bool,
+    ) -> (AccountId, Option<HashMap<AccountId, u64>>) {
+        // NFTs whose type can't be resolved (legacy token_ids) have no holdings cap, so aren't tracked
+        let token_type_id = self.internal_token_type_for_token(token_id).map(|(token_type_id, token_type)| {
+            self.internal_assert_holdings_cap(token_type_id, &token_type, receiver_id);
+            token_type_id
+        });
+        if self.require_receiver_registration {
+            require!(self.storage_deposits.contains_key(receiver_id), ERR_RECEIVER_NOT_REGISTERED);
+        }
+        if let Some(locker_id) = self.locked_by_id.get(token_id) {
+            require!(sender_id == &locker_id, format!("{} {}", ERR_TOKEN_LOCKED_BY, locker_id));
+        }
+
+        let (old_owner, old_approvals) = self.tokens_mut().internal_transfer(sender_id, receiver_id, token_id, approval_id, memo);
+        // the lock doesn't carry over to the new owner
+        if !keep_lock {
+            self.internal_remove_lock(token_id, &old_owner);
+        }
+
+        if let Some(token_type_id) = token_type_id {
+            self.internal_decrement_holdings(token_type_id, &old_owner);
+            self.internal_increment_holdings(token_type_id, receiver_id);
+        }
+
+        (old_owner, old_approvals)
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenResolver for Contract {
+    /// Callback of `nft_transfer_call`: resolves via `NonFungibleToken::nft_resolve_transfer`, moving holdings back to
+    /// `previous_owner_id` if the NFT was returned (keeping any lock). Otherwise removes any lock, refunding `previous_owner_id`
+    #[private]
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: Option<HashMap<AccountId, u64>>,
+    ) -> bool {
+        let token_type_id = try_token_type_id_from_token_id(&token_id);
+        let transferred = self.tokens_mut().nft_resolve_transfer(previous_owner_id.clone(), receiver_id.clone(), token_id.clone(), approved_account_ids);
+        if transferred {
+            self.internal_remove_lock(&token_id, &previous_owner_id);
+        } else if let Some(token_type_id) = token_type_id {
+            self.internal_decrement_holdings(token_type_id, &receiver_id);
+            self.internal_increment_holdings(token_type_id, &previous_owner_id);
+        }
+        transferred
+    }
 }
 
 impl NonFungibleTokenResolver for NonFungibleToken {
@@ -1,44 +1,173 @@
 use crate::*;
-use near_sdk::{log};
+use near_sdk::{require, Balance, Promise};
 
 pub type TokenTypeId = u64;
 pub type TokenTypeTitle = String;
 
-pub type AssetDetail = Vec<String>; // Vec with 3 x string elements. E.g. ["1.jpg", "10", "1.json"] where 1.jpg is asset filename 10 is supply_remaining, and "1.json" is json filename. (final element should be empty string if no json is available)
+pub type AssetDetail = Vec<String>; // Vec with 3 x string elements. E.g. ["1.jpg", "10", "1.json"] where 1.jpg is asset filename 10 is supply_remaining, and "1.json" is json filename. (final element should be empty string if no json is available). On creation, an optional 4th element may provide the hex-encoded sha256 of the asset file, which is stored in `asset_hashes_by_type` rather than here
 pub type TokenTypeAssets = Vec<AssetDetail>;
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct TokenTypeV1 {
-	pub metadata: TokenTypeMetadata,
+	pub metadata: TokenTypeMetadataV1,
 	pub owner_id: AccountId,
 	pub royalty: HashMap<AccountId, u32>,
 	pub tokens: UnorderedSet<TokenId>,
 	pub approved_market_id: Option<AccountId>,
 }
 
+/// layout stored by v2.1.0
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TokenTypeV2 {
+	pub metadata: TokenTypeMetadataV1,
+	pub owner_id: AccountId,
+	pub royalty: HashMap<AccountId, u32>,
+	pub tokens: UnorderedSet<TokenId>,
+	pub approved_market_id: Option<AccountId>,
+	pub asset_count: u64,
+	pub cover_asset: Option<String>,
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct TokenType {
+	/// `metadata.copies` is always `Some` (required by `nft_create_type`, and set to existing supply on migration of V1 types lacking it)
 	pub metadata: TokenTypeMetadata,
 	pub owner_id: AccountId,
 	pub royalty: HashMap<AccountId, u32>,
 	pub tokens: UnorderedSet<TokenId>,
 	pub approved_market_id: Option<AccountId>,
-	// NEW
 	pub asset_count: u64,
 	/// filename for cover asset located within IPFS directory (metadata.media)
 	pub cover_asset: Option<String>,
+	// NEW (since v2.1.0, see `TokenTypeV2`)
+	/// price (paid to `owner_id`, or split per `royalty` if `primary_sale_split`) for anyone to mint via `nft_public_mint`. `None` means type can only be minted by owner
+	pub price: Option<U128>,
+	/// `metadata.copies` at time of creation, preserved when copies are capped. Used as the edition denominator on enumeration (e.g. "Title — 2/10")
+	pub original_copies: Option<u64>,
+	/// true once `nft_cap_copies` has been called; `metadata.copies` then reflects the capped supply
+	pub capped: bool,
+	/// max number of NFTs of this type that a single account may hold (via mint or transfer). Type owner is exempt. `None` means no limit
+	pub max_holdings_per_account: Option<u64>,
+	/// number of NFTs of this type that have been burned (`tokens` only contains existing NFTs)
+	pub num_burned: u64,
+	/// if true, `price` paid on `nft_public_mint` (primary sale) is split across `royalty` recipients, with the remainder to `owner_id`
+	pub primary_sale_split: bool,
+	/// block timestamp (nanoseconds) at creation. 0 for types created before this was tracked
+	pub created_at: u64,
+	/// true once `nft_freeze_type` has been called; metadata, royalty & supply (copies/assets) can then never change
+	pub frozen: bool,
+	/// highest edition assigned by sequential mints (i.e. other than `nft_claim_reserved`), which skip reserved editions
+	pub last_edition: u64,
+	/// false while the type is hidden (e.g. before a drop reveal); `nft_tokens` & `nft_tokens_by_type` can then omit its NFTs. Set via `nft_set_type_revealed`
+	pub revealed: bool,
+	/// false while public minting (`nft_public_mint` & market lazy mints) is temporarily disabled via `nft_set_type_enabled`; owner mints still work. Unlike `frozen`, reversible
+	pub enabled: bool,
+}
+
+impl TokenType {
+	/// New type with no NFTs minted, as created by `nft_create_type`: `metadata.copies` is also its `original_copies`, and it is
+	/// revealed, enabled & owner-mint only (no `price`), with no market, holdings cap or primary sale split (set these on the result)
+	pub fn new(
+		token_type_id: TokenTypeId,
+		metadata: TokenTypeMetadata,
+		owner_id: AccountId,
+		royalty: HashMap<AccountId, u32>,
+		asset_count: u64,
+		cover_asset: String,
+	) -> Self {
+		let original_copies = metadata.copies;
+		TokenType {
+			metadata,
+			owner_id,
+			royalty,
+			tokens: UnorderedSet::new(
+				StorageKey::TokensByTypeInner {
+					token_type_id
+				}
+				.try_to_vec()
+				.unwrap(),
+			),
+			approved_market_id: None,
+			asset_count,
+			cover_asset: Some(cover_asset),
+			price: None,
+			original_copies,
+			capped: false,
+			max_holdings_per_account: None,
+			num_burned: 0,
+			primary_sale_split: false,
+			created_at: env::block_timestamp(),
+			frozen: false,
+			last_edition: 0,
+			revealed: true,
+			enabled: true,
+		}
+	}
+
+	/// number of NFTs of this type ever minted, including burned NFTs. Checked against `metadata.copies` on mint
+	pub fn num_minted(&self) -> u64 {
+		self.tokens.len() + self.num_burned
+	}
+
+	/// edition number (and token_id suffix) of the next NFT minted sequentially of this type, before skipping reserved editions.
+	/// Never reuses the edition of a burned NFT
+	pub fn next_edition(&self) -> u64 {
+		self.last_edition.checked_add(1).unwrap_or_else(|| env::panic_str(ERR_EDITION_OVERFLOW))
+	}
 }
 
 impl From<TokenTypeV1> for TokenType {
 	fn from(v1: TokenTypeV1) -> Self {
+		// V1 types may lack `copies`; cap these at their existing supply, so that `copies` is always `Some`
+		let original_copies = Some(v1.metadata.copies.unwrap_or_else(|| v1.tokens.len()));
+		// editions were always assigned sequentially before editions could be reserved
+		let last_edition = v1.tokens.len();
 		TokenType {
-			metadata: v1.metadata,
+			metadata: TokenTypeMetadata { copies: original_copies, ..v1.metadata.into() },
 			owner_id: v1.owner_id,
 			royalty: v1.royalty,
 			tokens: v1.tokens,
 			approved_market_id: v1.approved_market_id,
 			asset_count: 1, // all existing token types have 1 asset
 			cover_asset: None,
+			price: None,
+			original_copies, // capped copies of existing token types cannot be recovered
+			capped: false,
+			max_holdings_per_account: None,
+			num_burned: 0,
+			primary_sale_split: false,
+			created_at: 0,
+			frozen: false,
+			last_edition,
+			revealed: true,
+			enabled: true,
+		}
+	}
+}
+
+impl From<TokenTypeV2> for TokenType {
+	fn from(v2: TokenTypeV2) -> Self {
+		let original_copies = v2.metadata.copies;
+		let last_edition = v2.tokens.len();
+		TokenType {
+			metadata: v2.metadata.into(),
+			owner_id: v2.owner_id,
+			royalty: v2.royalty,
+			tokens: v2.tokens,
+			approved_market_id: v2.approved_market_id,
+			asset_count: v2.asset_count,
+			cover_asset: v2.cover_asset,
+			price: None, // existing token types are owner-mint only
+			original_copies, // capped copies of existing token types cannot be recovered
+			capped: false,
+			max_holdings_per_account: None,
+			num_burned: 0,
+			primary_sale_split: false,
+			created_at: 0,
+			frozen: false,
+			last_edition,
+			revealed: true,
+			enabled: true,
 		}
 	}
 }
@@ -46,26 +175,21 @@ impl From<TokenTypeV1> for TokenType {
 pub fn versioned_token_type_to_token_type(versioned_token_type: VersionedTokenType) -> TokenType {
 	match versioned_token_type {
 			VersionedTokenType::Current(current) => current,
-			VersionedTokenType::V1(v1) => {
-				TokenType {
-					metadata: v1.metadata,
-					owner_id: v1.owner_id,
-					royalty: v1.royalty,
-					tokens: v1.tokens,
-					approved_market_id: v1.approved_market_id,
-					asset_count: 1, // all existing token types have 1 asset
-					cover_asset: None,
-				}
-			}
-			// FINISH
+			VersionedTokenType::V2(v2) => TokenType::from(v2),
+			// single V1 conversion, shared with `internal_migrate_token_type` & unmigrated types in `token_type_by_id_v1`
+			VersionedTokenType::V1(v1) => TokenType::from(v1),
 	}
 }
 
 // #[derive(Serialize, Deserialize)]
 // #[serde(crate = "near_sdk::serde")]
+/// NB: new variants must be inserted directly before `Current`, so that previously stored `Current` values
+/// (serialized with the previous variant index) are deserialized as the newly added legacy variant.
+/// Only layouts that were actually deployed need a variant; fields added between releases go straight into `TokenType`
 #[derive(BorshDeserialize, BorshSerialize)]
 pub enum VersionedTokenType {
 		V1(TokenTypeV1),
+		V2(TokenTypeV2),
     Current(TokenType),
 }
 
@@ -75,6 +199,46 @@ pub struct TokenTypeJson {
 	pub metadata: TokenTypeMetadata,
 	pub owner_id: AccountId,
 	pub royalty: HashMap<AccountId, u32>,
+	pub price: Option<U128>,
+	/// `metadata.copies` at time of creation (`metadata.copies` reflects capped supply if `capped` is true)
+	pub original_copies: Option<u64>,
+	pub capped: bool,
+	pub max_holdings_per_account: Option<u64>,
+	pub primary_sale_split: bool,
+	/// market every NFT of this type is approved for on mint
+	pub approved_market_id: Option<AccountId>,
+	/// block timestamp (nanoseconds) at creation, as a string to avoid precision loss in JS. "0" for types created before this was tracked
+	pub created_at: U64,
+	/// true once `nft_freeze_type` has been called; metadata, royalty & supply can then never change
+	pub frozen: bool,
+	/// false while the type is hidden (see `nft_set_type_revealed`)
+	pub revealed: bool,
+	/// false while public minting is disabled (see `nft_set_type_enabled`)
+	pub enabled: bool,
+}
+
+impl From<TokenType> for TokenTypeJson {
+	fn from(mut token_type: TokenType) -> Self {
+		// cover asset is located within IPFS directory (metadata.media)
+		if token_type.cover_asset.is_some() {
+			token_type.metadata.media = Some(format!("{}/{}", token_type.metadata.media.unwrap(), token_type.cover_asset.unwrap()))
+		};
+		TokenTypeJson {
+			metadata: token_type.metadata,
+			owner_id: token_type.owner_id,
+			royalty: token_type.royalty,
+			price: token_type.price,
+			original_copies: token_type.original_copies,
+			capped: token_type.capped,
+			max_holdings_per_account: token_type.max_holdings_per_account,
+			primary_sale_split: token_type.primary_sale_split,
+			approved_market_id: token_type.approved_market_id,
+			created_at: token_type.created_at.into(),
+			frozen: token_type.frozen,
+			revealed: token_type.revealed,
+			enabled: token_type.enabled,
+		}
+	}
 }
 
 #[derive(Serialize, Deserialize)]
@@ -87,22 +251,69 @@ pub struct TypeMintArgs {
 /// methods for NFT type (otherwise known as "series")
 pub trait NonFungibleTokenType {
 
-  /// Create a new NFT type (aka series)
+  /// Create a new NFT type (aka series). If `royalty` is empty, the contract's `default_royalty` is used.
+  /// If `mint_all_to` is provided, all `copies` (at most `max_batch_size`) are minted to that account in the same transaction
+  /// (each selecting its asset as in `nft_mint_type`, so all asset supply ends up minted), logging a single `nft_mint` event.
+  /// Each asset may carry a 4th element, the hex-encoded sha256 of the asset file (see `nft_get_type_assets`)
   fn nft_create_type(
       &mut self,
       metadata: TokenTypeMetadata,
       royalty: HashMap<AccountId, u32>,
 			assets: TokenTypeAssets,
 			cover_asset: String,
+			price: Option<U128>,
+			max_holdings_per_account: Option<u64>,
+			mint_all_to: Option<AccountId>,
+			primary_sale_split: Option<bool>,
   );
 
-  /// Cap copies of an existing NFT type/series to currently minted supply
+  /// Cap copies of an existing NFT type/series to currently minted supply (and discard remaining asset supply)
+  ///
+  /// Logs a custom event (no NEP-171 equivalent exists), with schema:
+  /// `EVENT_JSON:{"standard":"nft_series","version":"1.0.0","event":"nft_type_supply_change","data":[{"token_type_id":"42","token_type_title":"Title","old_copies":10,"new_copies":3}]}`
 	fn nft_cap_copies(
 		&mut self,
 		token_type_title: TokenTypeTitle,
 	);
 
+  /// Append assets (of shape [filename, supply_remaining, extra]) to an existing NFT type/series, raising `copies` by their total supply.
+  /// Not allowed once the type is capped or frozen.
+  /// NB: adding assets to a single-asset type changes its NFT titles from edition format ("Title — 2/10") to filename format (e.g. "Title — cat" for cat.jpg)
+  fn nft_add_assets(
+      &mut self,
+      token_type_title: TokenTypeTitle,
+      new_assets: TokenTypeAssets,
+  );
+
+  /// Move unminted assets (by filename) of an existing NFT type/series into a new type, e.g. to reorganize a drop. The source's `copies`
+  /// shrink by the moved supply, and the new type's `copies` equal it. The new type takes its title (and any other provided fields) from
+  /// `new_metadata`, falling back to the source's `media`, so asset filenames still resolve; royalty, `price`, holdings cap and market
+  /// approval are copied from the source, and its cover asset is the first moved asset.
+  /// Not allowed once the source is capped or frozen, if any of the assets has minted NFTs (checked against the source's NFTs, so the
+  /// source may have at most `MAX_TOKENS_PER_SPLIT`), or if the source would be left with a single asset (changing its NFT titles)
+  ///
+  /// Logs the source's `nft_type_supply_change` event (see `nft_cap_copies`)
+  fn nft_split_type(
+      &mut self,
+      source_title: TokenTypeTitle,
+      new_metadata: TokenTypeMetadata,
+      asset_filenames: Vec<String>,
+  );
+
+  /// Permanently freeze an existing NFT type/series: its metadata, royalty and supply (copies & assets) can no longer be updated.
+  /// NFTs can still be minted up to `copies`
+  ///
+  /// Logs a custom event (no NEP-171 equivalent exists), with schema:
+  /// `EVENT_JSON:{"standard":"nft_series","version":"1.0.0","event":"nft_type_frozen","data":[{"token_type_id":"42","token_type_title":"Title"}]}`
+	fn nft_freeze_type(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+	);
+
   /// Update any metadata or royalty fields of an existing NFT type/series EXCEPT `copies`
+  ///
+  /// An empty `royalty` clears all royalties (e.g. for CC0 releases), so the seller receives 100% of payouts. NB: unlike `nft_create_type`,
+  /// this doesn't fall back to `default_royalty`
   fn nft_update_type(
       &mut self,
       token_type_title: TokenTypeTitle,
@@ -110,22 +321,114 @@ pub trait NonFungibleTokenType {
       royalty: Option<HashMap<AccountId, u32>>,
   );
 
+  /// Replace royalty of an existing NFT type/series, without touching its metadata
+  fn nft_update_royalty(
+      &mut self,
+      token_type_title: TokenTypeTitle,
+      royalty: HashMap<AccountId, u32>,
+  );
+
+  /// Replace cover asset (filename within the type's `media` directory) of an existing NFT type/series, without touching its
+  /// assets or the metadata of NFTs already minted
+  fn nft_set_cover_asset(
+      &mut self,
+      token_type_title: TokenTypeTitle,
+      cover_asset: String,
+  );
+
+  /// Hide (`revealed: false`) or reveal an existing type/series. NFTs of unrevealed types can be omitted from `nft_tokens` &
+  /// `nft_tokens_by_type` (with `include_unrevealed: false`), e.g. so marketplaces don't show them before a drop reveal
+  fn nft_set_type_revealed(
+      &mut self,
+      token_type_title: TokenTypeTitle,
+      revealed: bool,
+  );
+
+  /// Disable (`enabled: false`) or re-enable public minting (`nft_public_mint`, and lazy mints by the type's market via `nft_transfer_payout`) of an
+  /// existing type/series, e.g. to pause a drop.
+  /// Owner mints are unaffected. Unlike `nft_freeze_type`, this can be undone
+  fn nft_set_type_enabled(
+      &mut self,
+      token_type_title: TokenTypeTitle,
+      enabled: bool,
+  );
+
+  /// Set (or clear, if `None`) the market that every NFT of an existing type/series is approved for on mint, so it can be listed right away
+  fn nft_set_approved_market(
+      &mut self,
+      token_type_title: TokenTypeTitle,
+      market_id: Option<AccountId>,
+  );
+
   /// Mint an NFT for specified type/series
+  ///
+  /// `metadata` optionally overrides the type's `title` and/or `description` for this NFT only (e.g. for 1/1 pieces);
+  /// the edition suffix is still appended to the title. All other fields are ignored
+  ///
+  /// Storage is paid from `receiver_id`'s storage balance (see `storage_deposit`) if registered with enough available balance
+  ///
+  /// If `mint_id` (a client-supplied idempotency key, unique per type) was already used for this type, nothing is minted and the NFT
+  /// minted by the earlier call is returned instead (attached deposit is refunded), so a retried transaction can't double-mint
 	fn nft_mint_type(
 		&mut self,
 		token_type_title: TokenTypeTitle,
 		receiver_id: AccountId,
-    _metadata: Option<TokenMetadata>,
+    metadata: Option<TokenMetadata>,
+		mint_id: Option<String>,
 ) -> Token;
 
-	/// Mint a batch of NFTs for specified type/series
+	/// Mint an NFT for specified type/series as any account, paying the type's `price` (to type owner), the contract's `mint_fee` (to contract owner) and storage
+	/// (from caller's storage balance, if registered via `storage_deposit` with enough available balance)
+	fn nft_public_mint(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		receiver_id: Option<AccountId>,
+	) -> Token;
+
+	/// Reserve `edition` (not yet minted, and within `copies`) of specified type/series for `account_id` to mint later via
+	/// `nft_claim_reserved`. Sequential mints (`nft_mint_type`, `nft_public_mint` etc.) skip reserved editions
+	fn nft_reserve_edition(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		edition: u64,
+		account_id: AccountId,
+	);
+
+	/// Mint an edition reserved for the caller via `nft_reserve_edition`. No `price` or `mint_fee` is charged; storage is paid from
+	/// caller's storage balance (see `storage_deposit`) if registered with enough available balance, otherwise from attached deposit
+	fn nft_claim_reserved(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		edition: u64,
+	) -> Token;
+
+	/// Mint a batch of NFTs for specified type/series (one per receiver, at most `max_batch_size`). Storage for the whole batch is paid
+	/// from attached deposit, and a single `nft_mint` event is logged with an entry per receiver
 	fn nft_batch_mint_type(
 		&mut self,
 		token_type_title: TokenTypeTitle,
 		receiver_ids: Vec<AccountId>
 	) -> Vec<Token>;
 
+	/// Move an NFT to another type/series (e.g. if minted into the wrong type). As token_ids encode their type
+	/// (`{token_type_id}:{edition}`), the NFT is reissued to the same owner as the next edition of the new type.
+	///
+	/// Edge cases:
+	/// * the old token_id counts as burned on the old type (as by `nft_admin_burn`), so its edition & asset are never reissued
+	/// * the reissued NFT is assigned an asset from the new type (asset filenames are relative to the type's `media` directory)
+	/// * neither type may be frozen; the new type must have supply remaining, and its holdings cap (if any) applies to the token owner
+	/// * approvals on the old token_id are cleared (storage refunded to token owner), so any open listings are invalidated
+	fn nft_recategorize_token(
+		&mut self,
+		token_id: TokenId,
+		new_type_title: TokenTypeTitle,
+	) -> Token;
+
 	/// Delete an NFT type/series that is empty (no NFTs minted yet)
+	///
+	/// Logs a custom event (no NEP-171 equivalent exists), with schema:
+	/// `EVENT_JSON:{"standard":"nft_series","version":"1.0.0","event":"nft_delete_type","data":[{"token_type_id":"42","token_type_title":"Title"}]}`
+	/// NB: `token_type_id` is a string (as in token_ids), to avoid precision loss in JS
 	fn nft_delete_type(
 		&mut self,
 		token_type_title: TokenTypeTitle,
@@ -139,69 +442,85 @@ impl NonFungibleTokenType for Contract {
         &mut self,
         metadata: TokenTypeMetadata,
         royalty: HashMap<AccountId, u32>,
-				assets: TokenTypeAssets,
+				mut assets: TokenTypeAssets,
 				cover_asset: String, // filename for cover asset located within IPFS directory (metadata.media)
+				price: Option<U128>, // if provided, anyone can mint this type via `nft_public_mint` by paying `price` to the type owner
+				max_holdings_per_account: Option<u64>, // if provided, max number of NFTs of this type that a single account (other than type owner) may hold
+				mint_all_to: Option<AccountId>, // if provided, all copies are minted to this account right away
+				primary_sale_split: Option<bool>, // if true, `price` is split across `royalty` recipients on `nft_public_mint` (defaults to false)
     ) {
 
 		let initial_storage_usage = env::storage_usage();
 
 		// VALIDATION
     let owner_id = env::predecessor_account_id();
-		assert_eq!(owner_id.clone(), self.tokens().owner_id, "Unauthorized");
+		assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
 		// `title` required
 		let title = metadata.title.clone();
-		assert!(title.is_some(), "token_metadata.title is required");
+		assert!(title.is_some(), "{}", ERR_TITLE_REQUIRED);
+		// `title` must not contain reserved delimiters, which would corrupt token_id parsing & edition title assembly
+		self.internal_assert_valid_title(title.as_ref().unwrap());
 		// `copies` required
 		let copies = metadata.copies.clone();
-		assert!(copies.is_some(), "token_metadata.copies is required");
+		assert!(copies.is_some(), "{}", ERR_COPIES_REQUIRED);
+		require!(copies.unwrap() > 0, ERR_COPIES_ZERO);
+		if let Some(max_copies) = self.max_copies_per_type {
+			require!(copies.unwrap() <= max_copies, format!("token_metadata.copies must not exceed {}", max_copies));
+		}
 		// `media` required
 		let media = metadata.media.clone();
-		assert!(media.is_some(), "token_metadata.media is required");
+		assert!(media.is_some(), "{}", ERR_MEDIA_REQUIRED);
+		// `reference_hash` must be 32 bytes if `reference` is provided
+		metadata.assert_valid();
+		// fall back to `default_royalty` if no royalty provided
+		let royalty = if royalty.is_empty() { self.default_royalty.clone() } else { royalty };
+		// at most `MAX_ROYALTY_RECIPIENTS`, so payouts fit within gas limits
+		assert_valid_royalty(&royalty);
+		self.internal_assert_min_royalty(&royalty);
 
 		let token_type_id = self.token_type_by_id.len() + 1;
 
-		assert!(self.token_type_by_title.insert(&metadata.title.clone().unwrap(), &token_type_id).is_none(), "token_metadata.title exists");
+		assert!(!self.token_type_by_title.contains_key(&metadata.title.clone().unwrap()), "{}", ERR_TITLE_EXISTS);
 
-		assert!(!assets.is_empty(), "assets vector must not be empty");
+		assert!(!assets.is_empty(), "{}", ERR_ASSETS_EMPTY);
 
-		assert!(assets.len() <= metadata.copies.unwrap() as usize, "length of assets vector must not exceed copies");
+		assert!(assets.len() <= metadata.copies.unwrap() as usize, "{}", ERR_ASSETS_EXCEED_COPIES);
 
 		// sum of total_supply must be equal to `metadata.copies`
-		let mut total_supply = 0 as u64;
-		for asset_detail in assets.clone() { // TODO: may want to ultimately verify each of these elements on Spearmint instead to save on gas costs (also so we don't hit gas limit... FYI 10,000 is fine, but 50,000+ becomes problematic)
-			// verify asset filename exists
-			let asset_filename = asset_detail.get(0);
-			assert!(asset_filename.is_some(), "Asset filename must be provided");
-			// verify 3rd element ("extra") exists (should be empty string if no "extra" file is available for this asset)
-			let asset_extra = asset_detail.get(2);
-			assert!(asset_extra.is_some(), "3 elements must be provided in each sub-array of assets (if there is no 'extra'/json file available for this asset, 3rd element should be empty string.)");
-			let supply_remaining: u64 = asset_detail.get(1).unwrap().clone().parse().unwrap();
-			// tally total_supply to verify against metadata.copies
-			total_supply = total_supply + supply_remaining;
-		}
+		let total_supply = assert_valid_assets(&assets);
 		assert!(total_supply == metadata.copies.unwrap(), "Total supply must equal copies. Received {} total supply & {} copies", total_supply, metadata.copies.unwrap());
+		assert_valid_cover_asset(&assets, &cover_asset);
+		if mint_all_to.is_some() {
+			require!(
+				metadata.copies.unwrap() <= self.max_batch_size,
+				format!("copies must be less than or equal to {} to mint all copies on creation", self.max_batch_size)
+			);
+		}
+
+		// MUTATION (only once all validation has passed)
+		self.token_type_by_title.insert(&metadata.title.clone().unwrap(), &token_type_id);
 
 		let token_type = TokenType {
-			metadata,
-			owner_id,
-			royalty,
-			tokens: UnorderedSet::new(
-				StorageKey::TokensByTypeInner {
-					token_type_id
-				}
-				.try_to_vec()
-				.unwrap(),
-			),
-			approved_market_id: None,
-			asset_count: assets.len() as u64,
-			cover_asset: Some(cover_asset),
+			price,
+			max_holdings_per_account,
+			primary_sale_split: primary_sale_split.unwrap_or(false),
+			..TokenType::new(token_type_id, metadata, owner_id, royalty, assets.len() as u64, cover_asset)
 		};
 		let versioned_token_type = VersionedTokenType::from(VersionedTokenType::Current(token_type));
 
 		self.token_type_by_id.insert(&token_type_id, &versioned_token_type);
 
+		self.internal_store_asset_hashes(token_type_id, &mut assets);
 		self.token_type_assets_by_id.insert(&token_type_id, &assets);
 
+		if let Some(receiver_id) = mint_all_to {
+			let token_ids: Vec<TokenId> = (0..copies.unwrap())
+				.map(|_| self.internal_mint_type(token_type_id, receiver_id.clone(), None).token_id)
+				.collect();
+
+			emit_nft_mint(&receiver_id, &token_ids);
+		}
+
     refund_deposit(env::storage_usage() - initial_storage_usage);
   }
 
@@ -209,14 +528,206 @@ impl NonFungibleTokenType for Contract {
 		&mut self,
 		token_type_title: TokenTypeTitle,
 		) {
-		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "Unauthorized");
-		let token_type_id = self.token_type_by_title.get(&token_type_title).expect("no type");
-		let mut versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("no token");
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let mut versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
 		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
-		token_type.metadata.copies = Some(token_type.tokens.len());
+		assert_not_frozen(&token_type);
+		let old_copies = token_type.metadata.copies;
+		let new_copies = token_type.num_minted();
+		token_type.metadata.copies = Some(new_copies);
+		token_type.capped = true;
 		versioned_token_type = VersionedTokenType::from(VersionedTokenType::Current(token_type));
 		self.token_type_by_id.insert(&token_type_id, &versioned_token_type);
-		// TODO: remove assets vector?
+		// remove assets vector, so leftover asset supply can't be minted
+		self.token_type_assets_by_id.remove(&token_type_id);
+
+		env::log_str(format!("{}{}", EVENT_JSON, json!({
+			"standard": CUSTOM_EVENT_STANDARD,
+			"version": CUSTOM_EVENT_VERSION,
+			"event": "nft_type_supply_change",
+			"data": [
+				{
+					"token_type_id": token_type_id.to_string(),
+					"token_type_title": token_type_title,
+					"old_copies": old_copies,
+					"new_copies": new_copies,
+				}
+			]
+		})).as_ref());
+	}
+
+	#[payable]
+	fn nft_add_assets(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		mut new_assets: TokenTypeAssets,
+	) {
+		let initial_storage_usage = env::storage_usage();
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+		require!(!new_assets.is_empty(), ERR_NEW_ASSETS_EMPTY);
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+		assert_not_frozen(&token_type);
+		require!(!token_type.capped, ERR_TYPE_CAPPED);
+
+		let added_supply = assert_valid_assets(&new_assets);
+		let copies = token_type.metadata.copies.unwrap_or(0).checked_add(added_supply).unwrap_or_else(|| env::panic_str(ERR_COPIES_OVERFLOW));
+		if let Some(max_copies) = self.max_copies_per_type {
+			require!(copies <= max_copies, format!("token_metadata.copies must not exceed {}", max_copies));
+		}
+
+		self.internal_store_asset_hashes(token_type_id, &mut new_assets);
+		let mut assets = self.token_type_assets_by_id.get(&token_type_id).unwrap_or_default();
+		assets.extend(new_assets.iter().cloned());
+		self.token_type_assets_by_id.insert(&token_type_id, &assets);
+
+		token_type.metadata.copies = Some(copies);
+		token_type.original_copies = Some(copies); // not capped, so original copies tracks copies
+		token_type.asset_count += new_assets.len() as u64;
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+
+		refund_or_reward_storage(initial_storage_usage);
+	}
+
+	#[payable]
+	fn nft_split_type(
+		&mut self,
+		source_title: TokenTypeTitle,
+		mut new_metadata: TokenTypeMetadata,
+		asset_filenames: Vec<String>,
+	) {
+		let initial_storage_usage = env::storage_usage();
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+		require!(!asset_filenames.is_empty(), "asset_filenames must not be empty");
+
+		let source_type_id = self.token_type_by_title.get(&source_title).expect(ERR_NO_TYPE);
+		self.internal_migrate_token_type(source_type_id);
+		let versioned_token_type = self.token_type_by_id.get(&source_type_id).expect(ERR_NO_TYPE);
+		let mut source_type = versioned_token_type_to_token_type(versioned_token_type);
+		assert_not_frozen(&source_type);
+		require!(!source_type.capped, ERR_TYPE_CAPPED);
+		require!(
+			source_type.tokens.len() <= MAX_TOKENS_PER_SPLIT,
+			format!("Cannot split a type with more than {} NFTs", MAX_TOKENS_PER_SPLIT)
+		);
+
+		let title = new_metadata.title.clone().unwrap_or_else(|| env::panic_str(ERR_TITLE_REQUIRED));
+		self.internal_assert_valid_title(&title);
+		assert!(!self.token_type_by_title.contains_key(&title), "{}", ERR_TITLE_EXISTS);
+		new_metadata.assert_valid();
+
+		// split source assets into moved & remaining
+		let mut remaining_assets = self.token_type_assets_by_id.get(&source_type_id).expect(ERR_NO_ASSETS);
+		let mut moved_assets: TokenTypeAssets = Vec::new();
+		for filename in asset_filenames.iter() {
+			let idx = remaining_assets.iter().position(|asset_detail| &asset_detail[0] == filename)
+				.unwrap_or_else(|| env::panic_str(&format!("Asset {} not found in {} (or has no supply remaining)", filename, source_title)));
+			moved_assets.push(remaining_assets.remove(idx));
+		}
+		require!(!remaining_assets.is_empty(), "Cannot move all assets of a type; use nft_update_type to rename it instead");
+		// NFT titles of a multi-asset type are in filename format, but edition format once it has a single asset (see `internal_assemble_token_metadata`)
+		let remaining_asset_count = source_type.asset_count.saturating_sub(moved_assets.len() as u64);
+		require!(remaining_asset_count > 1, "Cannot leave the source with a single asset, as that would change the titles of its NFTs");
+		// assets that were partially minted stay with the source, whose NFTs reference them (bounded by `MAX_TOKENS_PER_SPLIT`)
+		for token_id in source_type.tokens.iter() {
+			let media = self.tokens().token_metadata_by_id.as_ref()
+				.and_then(|by_id| by_id.get(&token_id))
+				.and_then(|versioned_metadata| versioned_token_metadata_to_token_metadata(versioned_metadata).media);
+			if let Some(media) = media {
+				require!(!asset_filenames.contains(&media), format!("Asset {} has already been minted ({})", media, token_id));
+			}
+		}
+		let moved_supply = assert_valid_assets(&moved_assets);
+		if let Some(copies) = new_metadata.copies {
+			require!(copies == moved_supply, format!("Total supply must equal copies. Received {} total supply & {} copies", moved_supply, copies));
+		}
+
+		// MUTATION
+		let old_copies = source_type.metadata.copies.unwrap_or_else(|| env::panic_str("Cannot split a type without copies"));
+		let new_copies = old_copies.checked_sub(moved_supply).unwrap_or_else(|| env::panic_str(&format!("Cannot move supply of {} from a type with {} copies", moved_supply, old_copies)));
+		source_type.metadata.copies = Some(new_copies);
+		source_type.original_copies = Some(new_copies); // not capped, so original copies tracks copies
+		source_type.asset_count = remaining_asset_count;
+
+		let token_type_id = self.token_type_by_id.len() + 1;
+		new_metadata.copies = Some(moved_supply);
+		if new_metadata.media.is_none() {
+			new_metadata.media = source_type.metadata.media.clone();
+		}
+		let token_type = TokenType {
+			approved_market_id: source_type.approved_market_id.clone(),
+			price: source_type.price,
+			max_holdings_per_account: source_type.max_holdings_per_account,
+			primary_sale_split: source_type.primary_sale_split,
+			..TokenType::new(
+				token_type_id,
+				new_metadata,
+				source_type.owner_id.clone(),
+				source_type.royalty.clone(),
+				moved_assets.len() as u64,
+				moved_assets[0][0].clone(),
+			)
+		};
+		self.token_type_by_title.insert(&title, &token_type_id);
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+		self.token_type_assets_by_id.insert(&token_type_id, &moved_assets);
+		self.token_type_by_id.insert(&source_type_id, &VersionedTokenType::Current(source_type));
+		self.token_type_assets_by_id.insert(&source_type_id, &remaining_assets);
+
+		// hashes follow their assets
+		if let Some(mut source_hashes) = self.asset_hashes_by_type.get(&source_type_id) {
+			let moved_hashes: HashMap<String, String> = asset_filenames.iter()
+				.filter_map(|filename| source_hashes.remove(filename).map(|hash| (filename.clone(), hash)))
+				.collect();
+			if !moved_hashes.is_empty() {
+				self.asset_hashes_by_type.insert(&source_type_id, &source_hashes);
+				self.asset_hashes_by_type.insert(&token_type_id, &moved_hashes);
+			}
+		}
+
+		refund_or_reward_storage(initial_storage_usage);
+
+		env::log_str(format!("{}{}", EVENT_JSON, json!({
+			"standard": CUSTOM_EVENT_STANDARD,
+			"version": CUSTOM_EVENT_VERSION,
+			"event": "nft_type_supply_change",
+			"data": [
+				{
+					"token_type_id": source_type_id.to_string(),
+					"token_type_title": source_title,
+					"old_copies": old_copies,
+					"new_copies": new_copies,
+				}
+			]
+		})).as_ref());
+	}
+
+	fn nft_freeze_type(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		) {
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+		assert_not_frozen(&token_type);
+		token_type.frozen = true;
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+
+		env::log_str(format!("{}{}", EVENT_JSON, json!({
+			"standard": CUSTOM_EVENT_STANDARD,
+			"version": CUSTOM_EVENT_VERSION,
+			"event": "nft_type_frozen",
+			"data": [
+				{
+					"token_type_id": token_type_id.to_string(),
+					"token_type_title": token_type_title,
+				}
+			]
+		})).as_ref());
 	}
 
 	#[payable]
@@ -228,20 +739,19 @@ impl NonFungibleTokenType for Contract {
     ) {
 		let initial_storage_usage = env::storage_usage();
     let owner_id = env::predecessor_account_id();
-		assert_eq!(owner_id.clone(), self.tokens().owner_id, "Unauthorized");
+		assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
 
-		let token_type_id = self.token_type_by_title.get(&token_type_title).expect("no type");
-		let mut versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("no token");
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let mut versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
 		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+		assert_not_frozen(&token_type);
+		let old_title = token_type.metadata.title.clone().unwrap();
 
+		// VALIDATION & in-memory updates only; storage is mutated once all validation has passed
 		if let Some(metadata) = metadata {
-			if metadata.title.is_some() && metadata.title.clone().unwrap() != token_type.metadata.title.clone().unwrap() {
-				assert_eq!(self.token_type_by_title.contains_key(&metadata.title.clone().unwrap()), false, "token_metadata.title already exists");
-
-				// update token_type_by_title
-				self.token_type_by_title.remove(&token_type.metadata.title.clone().unwrap());
-				self.token_type_by_title.insert(&metadata.title.clone().unwrap(), &token_type_id);
-
+			if metadata.title.is_some() && metadata.title.clone().unwrap() != old_title {
+				assert_eq!(self.token_type_by_title.contains_key(&metadata.title.clone().unwrap()), false, "{}", ERR_TITLE_EXISTS);
+				self.internal_assert_valid_title(metadata.title.as_ref().unwrap());
 				token_type.metadata.title = metadata.title;
 			}
 			// don't validate that description is_some, as description can be none
@@ -254,52 +764,482 @@ impl NonFungibleTokenType for Contract {
 			// don't allow to patch asset_distribution for now
 		}
 		if let Some(royalty) = royalty {
+			assert_valid_royalty(&royalty);
+			self.internal_assert_min_royalty(&royalty);
 			token_type.royalty = royalty
 		}
+
+		// MUTATION
+		let new_title = token_type.metadata.title.clone().unwrap();
+		if new_title != old_title {
+			// update token_type_by_title
+			self.token_type_by_title.remove(&old_title);
+			self.token_type_by_title.insert(&new_title, &token_type_id);
+		}
 		// convert back to versioned
 		versioned_token_type = VersionedTokenType::from(VersionedTokenType::Current(token_type));
 
 		self.token_type_by_id.insert(&token_type_id, &versioned_token_type);
 
-		let amt_to_refund = if env::storage_usage() > initial_storage_usage { env::storage_usage() - initial_storage_usage } else { initial_storage_usage - env::storage_usage() };
-    refund_deposit(amt_to_refund);
+		refund_or_reward_storage(initial_storage_usage);
   }
 
+	#[payable]
+	fn nft_update_royalty(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		royalty: HashMap<AccountId, u32>,
+	) {
+		let initial_storage_usage = env::storage_usage();
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+		assert_valid_royalty(&royalty);
+		self.internal_assert_min_royalty(&royalty);
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+		assert_not_frozen(&token_type);
+		token_type.royalty = royalty;
+
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+
+		refund_or_reward_storage(initial_storage_usage);
+	}
+
+	#[payable]
+	fn nft_set_cover_asset(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		cover_asset: String,
+	) {
+		let initial_storage_usage = env::storage_usage();
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+		require!(!cover_asset.is_empty(), ERR_COVER_ASSET_EMPTY);
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+		assert_not_frozen(&token_type);
+		token_type.cover_asset = Some(cover_asset);
+
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+
+		refund_or_reward_storage(initial_storage_usage);
+	}
+
+	fn nft_set_type_revealed(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		revealed: bool,
+	) {
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+		token_type.revealed = revealed;
+
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+	}
+
+	fn nft_set_type_enabled(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		enabled: bool,
+	) {
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+		token_type.enabled = enabled;
+
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+	}
+
+	#[payable]
+	fn nft_set_approved_market(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		market_id: Option<AccountId>,
+	) {
+		let initial_storage_usage = env::storage_usage();
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+		token_type.approved_market_id = market_id;
+
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(token_type));
+
+		refund_or_reward_storage(initial_storage_usage);
+	}
+
 	#[payable]
 	fn nft_mint_type(
 		&mut self,
 		token_type_title: TokenTypeTitle,
 		receiver_id: AccountId,
-    _metadata: Option<TokenMetadata>,
+    metadata: Option<TokenMetadata>,
+		mint_id: Option<String>,
 		) -> Token {
 
-		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "Unauthorized");
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
 
 		let initial_storage_usage = env::storage_usage();
 
-		// get token type & mint args
-		let token_type_id = self.token_type_by_title.get(&token_type_title).expect("no type");
-		let mut versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("no token");
-		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		// namespaced per type, so clients only need mint_ids to be unique within a type
+		let mint_key = mint_id.map(|mint_id| format!("{}{}{}", token_type_id, TOKEN_DELIMETER, mint_id));
+		if let Some(token_id) = mint_key.as_ref().and_then(|mint_key| self.token_id_by_mint_key.get(mint_key)) {
+			refund_deposit(0);
+			return self.nft_token(token_id.clone()).unwrap_or_else(|| env::panic_str(&format!("mint_id was used to mint {}, which has since been burned", token_id)));
+		}
+		self.internal_migrate_token_type(token_type_id);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let token_type = versioned_token_type_to_token_type(versioned_token_type);
+		assert_eq!(&env::predecessor_account_id(), &token_type.owner_id, "{}", ERR_NOT_TYPE_OWNER);
+
+		let token = self.internal_mint_type(token_type_id, receiver_id.clone(), metadata);
+		if let Some(mint_key) = mint_key {
+			self.token_id_by_mint_key.insert(&mint_key, &token.token_id);
+		}
+
+		// storage is drawn from receiver's storage balance (see `storage_deposit`) if it covers it, otherwise from attached deposit
+		let storage_used = env::storage_usage() - initial_storage_usage;
+		let storage_drawn = self.internal_draw_storage_balance(&receiver_id, storage_used);
+    refund_deposit(if storage_drawn { 0 } else { storage_used });
+
+		emit_nft_mint(&receiver_id, &[token.token_id.clone()]);
+			
+		token
+	}
+
+	#[payable]
+	fn nft_public_mint(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		receiver_id: Option<AccountId>,
+		) -> Token {
+		let initial_storage_usage = env::storage_usage();
+		let predecessor_account_id = env::predecessor_account_id();
+		let receiver_id = receiver_id.unwrap_or_else(|| predecessor_account_id.clone());
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let token_type = versioned_token_type_to_token_type(versioned_token_type);
+		let price: Balance = token_type.price.expect(ERR_NOT_PUBLIC_MINT).into();
+		require!(token_type.enabled, ERR_TYPE_DISABLED);
+		let type_owner_id = token_type.owner_id;
+		// primary sale proceeds go to type owner, or are split per royalty (with the remainder to type owner)
+		let mut proceeds: HashMap<AccountId, Balance> = HashMap::new();
+		let mut royalty_paid: Balance = 0;
+		if token_type.primary_sale_split {
+			for (account_id, royalty_percentage) in token_type.royalty.iter() {
+				let amount: Balance = royalty_to_payout(*royalty_percentage, price).into();
+				*proceeds.entry(account_id.clone()).or_default() += amount;
+				royalty_paid += amount;
+			}
+		}
+		*proceeds.entry(type_owner_id).or_default() += price - royalty_paid;
+
+		let token = self.internal_mint_type(token_type_id, receiver_id.clone(), None);
+
+		// attached deposit is made up of three separate components: price (to type owner/royalty recipients), mint fee (to contract owner) & storage (kept by contract)
+		// storage is drawn from caller's storage balance (see `storage_deposit`) instead, if it covers it
+		let mint_fee: Balance = self.mint_fee.map(|mint_fee| mint_fee.into()).unwrap_or(0);
+		let storage_used = env::storage_usage() - initial_storage_usage;
+		let storage_cost = if self.internal_draw_storage_balance(&predecessor_account_id, storage_used) {
+			0
+		} else {
+			env::storage_byte_cost() * Balance::from(storage_used)
+		};
+		let required_deposit = price + mint_fee + storage_cost;
+		let attached_deposit = env::attached_deposit();
+		require!(
+			attached_deposit >= required_deposit,
+			format!("Must attach {} yoctoNEAR ({} price + {} mint fee + {} storage)", required_deposit, price, mint_fee, storage_cost)
+		);
+
+		for (account_id, amount) in proceeds {
+			if amount > 0 {
+				Promise::new(account_id).transfer(amount);
+			}
+		}
+		if mint_fee > 0 {
+			Promise::new(self.tokens().owner_id.clone()).transfer(mint_fee);
+		}
+		let refund = attached_deposit - required_deposit;
+		if refund > 1 {
+			Promise::new(predecessor_account_id).transfer(refund);
+		}
+
+		emit_nft_mint(&receiver_id, &[token.token_id.clone()]);
+
+		token
+	}
+
+	#[payable]
+	fn nft_reserve_edition(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		edition: u64,
+		account_id: AccountId,
+	) {
+		let initial_storage_usage = env::storage_usage();
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		self.internal_migrate_token_type(token_type_id);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let token_type = versioned_token_type_to_token_type(versioned_token_type);
+		let copies = token_type.metadata.copies.unwrap_or(u64::MAX);
+		require!(edition >= 1 && edition <= copies, format!("Edition {} is out of range (1 to {})", edition, copies));
+		require!(edition > token_type.last_edition, format!("Edition {} has already been minted", edition));
+		require!(!self.internal_is_edition_reserved(token_type_id, edition), format!("Edition {} is already reserved", edition));
+
+		self.reserved_editions.insert(&(token_type_id, edition), &account_id);
+
+		refund_or_reward_storage(initial_storage_usage);
+	}
+
+	#[payable]
+	fn nft_claim_reserved(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		edition: u64,
+	) -> Token {
+		let initial_storage_usage = env::storage_usage();
+		let receiver_id = env::predecessor_account_id();
+
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let reserved_for = self.reserved_editions.get(&(token_type_id, edition));
+		require!(reserved_for.as_ref() == Some(&receiver_id), format!("Edition {} is not reserved for {}", edition, receiver_id));
+
+		self.reserved_editions.remove(&(token_type_id, edition));
+		self.claimed_editions.insert(&(token_type_id, edition));
+		let token = self.internal_mint_type_edition(token_type_id, receiver_id.clone(), None, Some(edition));
+
+		let storage_used = env::storage_usage() - initial_storage_usage;
+		let storage_drawn = self.internal_draw_storage_balance(&receiver_id, storage_used);
+		refund_deposit(if storage_drawn { 0 } else { storage_used });
+
+		emit_nft_mint(&receiver_id, &[token.token_id.clone()]);
 
-		assert_eq!(&env::predecessor_account_id(), &token_type.owner_id, "not type owner");
+		token
+	}
+
+	#[payable]
+	fn nft_batch_mint_type(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+		receiver_ids: Vec<AccountId>
+	) -> Vec<Token>
+	{
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+		let initial_storage_usage = env::storage_usage();
+
+		// Check length of receiver_ids
+		require!(
+			receiver_ids.len() as u64 <= self.max_batch_size,
+			format!("receiver_ids must be less than or equal to {}", self.max_batch_size)
+		);
+
+		// fail upfront with a clear message, rather than with "type supply maxed" partway through the batch
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		self.internal_migrate_token_type(token_type_id);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let token_type = versioned_token_type_to_token_type(versioned_token_type);
+		assert_eq!(&env::predecessor_account_id(), &token_type.owner_id, "{}", ERR_NOT_TYPE_OWNER);
+		let requested = receiver_ids.len() as u64;
+		let available = self.internal_available_supply(token_type_id, &token_type, requested);
+		require!(
+			requested <= available,
+			format!("batch exceeds remaining supply: requested {}, available {}", requested, available)
+		);
+
+		let mut tokens = Vec::new();
+		// (owner, token_ids) in order of first appearance, for the event
+		let mut token_ids_by_owner: Vec<(AccountId, Vec<TokenId>)> = Vec::new();
+		for receiver_id in receiver_ids {
+			let token = self.internal_mint_type(token_type_id, receiver_id.clone(), None);
+			match token_ids_by_owner.iter_mut().find(|(id, _)| id == &receiver_id) {
+				Some((_, owner_token_ids)) => owner_token_ids.push(token.token_id.clone()),
+				None => token_ids_by_owner.push((receiver_id, vec![token.token_id.clone()])),
+			}
+			tokens.push(token);
+		}
+
+		// storage for the whole batch is paid from attached deposit
+		refund_deposit(env::storage_usage() - initial_storage_usage);
+
+		emit_nft_mint_batch(&token_ids_by_owner);
+
+		tokens
+	}
+
+	#[payable]
+	fn nft_recategorize_token(
+		&mut self,
+		token_id: TokenId,
+		new_type_title: TokenTypeTitle,
+	) -> Token {
+		let initial_storage_usage = env::storage_usage();
+		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
+
+		let old_type_id = token_type_id_from_token_id(&token_id);
+		let new_type_id = self.token_type_by_title.get(&new_type_title).expect(ERR_NO_TYPE);
+		assert_ne!(old_type_id, new_type_id, "{}", ERR_TOKEN_ALREADY_IN_TYPE);
+		self.internal_migrate_token_type(old_type_id);
+		self.internal_migrate_token_type(new_type_id);
+
+		let versioned_token_type = self.token_type_by_id.get(&old_type_id).expect(ERR_NO_TYPE);
+		let mut old_type = versioned_token_type_to_token_type(versioned_token_type);
+		assert_not_frozen(&old_type);
+		let versioned_token_type = self.token_type_by_id.get(&new_type_id).expect(ERR_NO_TYPE);
+		assert_not_frozen(&versioned_token_type_to_token_type(versioned_token_type));
+
+		// remove from old type
+		let (owner_id, token_metadata, approved_account_ids) = self.tokens_mut().internal_remove(&token_id);
+		let lock_storage = self.internal_remove_lock(&token_id, &owner_id);
+		let mut approvals_storage: u64 = 0;
+		if let Some(approved_account_ids) = approved_account_ids {
+			approvals_storage = approved_account_ids.keys().map(bytes_for_approved_account_id).sum();
+			refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+		}
+		// counted as burned (as by `internal_burn`), so the old edition is never reissued
+		old_type.tokens.remove(&token_id);
+		old_type.num_burned += 1;
+		self.num_burned += 1;
+		self.token_type_by_id.insert(&old_type_id, &VersionedTokenType::Current(old_type));
+		self.internal_decrement_holdings(old_type_id, &owner_id);
+		let token_metadata = token_metadata.map(TokenMetadata::from);
+
+		// reissue as next edition of new type (keeping any title/description override)
+		let token = self.internal_mint_type(new_type_id, owner_id.clone(), token_metadata);
+
+		// approvals & lock storage have already been refunded to the token owner, who paid for them
+		refund_or_reward_storage(initial_storage_usage - approvals_storage - lock_storage);
+
+		env::log_str(format!("{}{}", EVENT_JSON, json!({
+			"standard": "nep171",
+			"version": "1.0.0",
+			"event": "nft_burn",
+			"data": [
+				{
+					"owner_id": owner_id,
+					"token_ids": [token_id],
+					"memo": format!("recategorized as {}", token.token_id),
+				}
+			]
+		})).as_ref());
+		emit_nft_mint(&owner_id, &[token.token_id.clone()]);
+
+		token
+	}
+
+	#[payable]
+	fn nft_delete_type(
+		&mut self,
+		token_type_title: TokenTypeTitle,
+	) {
+		let initial_storage_usage = env::storage_usage();
+    let owner_id = env::predecessor_account_id();
+		assert_eq!(owner_id.clone(), self.tokens().owner_id, "{}", ERR_UNAUTHORIZED);
 
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let token_type = versioned_token_type_to_token_type(versioned_token_type);
+		
+		// check if there are any tokens (can't delete if there are minted NFTs)
 		let num_tokens = token_type.tokens.len();
+		assert!(num_tokens < 1, "{} (found {} tokens)", ERR_TYPE_HAS_TOKENS, num_tokens);
+
+		// remove from token_type_by_id
+		self.token_type_by_id.remove(&token_type_id);
+		// remove from token_type_by_title
+		self.token_type_by_title.remove(&token_type_title);
+		// remove from token_type_assets_by_id & asset_hashes_by_type
+		self.token_type_assets_by_id.remove(&token_type_id);
+		self.asset_hashes_by_type.remove(&token_type_id);
+
+		refund_or_reward_storage(initial_storage_usage);
+
+		env::log_str(format!("{}{}", EVENT_JSON, json!({
+			"standard": CUSTOM_EVENT_STANDARD,
+			"version": CUSTOM_EVENT_VERSION,
+			"event": "nft_delete_type",
+			"data": [
+				{
+					"token_type_id": token_type_id.to_string(),
+					"token_type_title": token_type_title,
+				}
+			]
+		})).as_ref());
+	}
+}
+
+impl Contract {
+	/// Mint the next NFT of a type/series to `receiver_id`, selecting a random asset from the type's assets vector.
+	///
+	/// Performs no authorization checks, storage refunds or event logging; these are the responsibility of the caller.
+	///
+	/// Only `title` & `description` of `metadata` (if provided) are stored on the NFT, overriding the type's title/description on `nft_token`.
+	///
+	/// If the type has an `approved_market_id`, the NFT is approved for it (storage to be paid by the caller).
+	///
+	/// Returns the NFT as returned by `nft_token` (metadata assembled from the type, e.g. `media` as "<TokenType.media>/<filename>")
+	pub(crate) fn internal_mint_type(&mut self, token_type_id: TokenTypeId, receiver_id: AccountId, metadata: Option<TokenMetadata>) -> Token {
+		self.internal_mint_type_edition(token_type_id, receiver_id, metadata, None)
+	}
+
+	/// As `internal_mint_type`, minting `edition` if provided (a reserved edition being claimed; caller is responsible for checking the
+	/// reservation). Otherwise mints the next sequential edition, skipping reserved editions
+	pub(crate) fn internal_mint_type_edition(&mut self, token_type_id: TokenTypeId, receiver_id: AccountId, metadata: Option<TokenMetadata>, edition: Option<u64>) -> Token {
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let mut token_type = versioned_token_type_to_token_type(versioned_token_type);
+
+		let num_tokens = token_type.num_minted();
 		let max_copies = token_type.metadata.copies.unwrap_or(u64::MAX);
-		assert_ne!(num_tokens, max_copies, "type supply maxed");
+		assert!(num_tokens < max_copies, "{}", ERR_TYPE_SUPPLY_MAXED);
+
+		let edition = match edition {
+			Some(edition) => edition,
+			None => {
+				let edition = self.internal_next_sequential_edition(token_type_id, &token_type);
+				assert!(edition <= max_copies, "{}", ERR_EDITIONS_RESERVED);
+				token_type.last_edition = edition;
+				edition
+			}
+		};
+
+		self.internal_assert_holdings_cap(token_type_id, &token_type, &receiver_id);
+		self.internal_increment_holdings(token_type_id, &receiver_id);
+		let minted_count = self.minted_to.get(&(token_type_id, receiver_id.clone())).unwrap_or(0);
+		self.minted_to.insert(&(token_type_id, receiver_id.clone()), &(minted_count + 1));
 		
+		let (title, description) = metadata.map(|metadata| (metadata.title, metadata.description)).unwrap_or_default();
 		let mut final_metadata = TokenMetadata {
-			title: None, // this remains None; NFT title is taken from token_type on enumeration so there is no need to store it on individual token metadata as well
-			description: None, // this remains None; NFT description is taken from token_type on enumeration so there is no need to store it on individual token metadata as well
+			title, // None unless overridden for this NFT (e.g. 1/1 pieces); otherwise NFT title is taken from token_type on enumeration so there is no need to store it on individual token metadata as well
+			description, // None unless overridden for this NFT (e.g. 1/1 pieces); otherwise NFT description is taken from token_type on enumeration so there is no need to store it on individual token metadata as well
 			media: None, // initiate as None. If this is an updated v1 type or a post-v1 type, meaning `assets` array is present, `media` will become the asset filename that can be located inside the token_type directory CID (this directory CID is stored as `media` on token_type). E.g. "cat.jpg" => on enumeration, TokenMetadata.media will read "<TokenType.media>/<TokenMetadata.media>", e.g. "abcd1234/cat.jpg"
 			copies: None, // this remains None; NFT copies is taken from token_type on enumeration so there is no need to store it on individual token metadata as well
 			extra: None, // this will become the "extra" (e.g. off-chain json) filename that can be located inside the token_type directory CID (this directory CID is stored as `media` on token_type). E.g. "cat.json" (doesn't have to correspond to filename of media asset, btw) => on enumeration, TokenMetadata.extra will read "<TokenType.media>/<TokenMetadata.extra>", e.g. "abcd1234/cat.json"
+			updated_at: Some(env::block_timestamp().into()),
 		};
 
 		// get the assets vector for this token_type; let the fun begin!
-		let mut assets = self.token_type_assets_by_id.get(&token_type_id).expect("No assets");
+		let mut assets = self.token_type_assets_by_id.get(&token_type_id).expect(ERR_NO_ASSETS);
 
-		let random_num = random_u128();
+		// salt with token_id so each mint within a batch (same block seed) selects its asset independently
+		let token_id = format!("{}{}{}", &token_type_id, TOKEN_DELIMETER, edition);
+		let random_num = random_u128(token_id.as_bytes());
 		let random_asset_idx = random_num % assets.len() as u128;
 		let mut asset_detail = assets.get(random_asset_idx as usize).unwrap().clone();
 		let asset_filename = asset_detail.get(0).unwrap().clone(); // first element is filename of media asset stored inside IPFS directory
@@ -327,85 +1267,196 @@ impl NonFungibleTokenType for Contract {
 		
 		final_metadata.media = Some(asset_filename.to_string());
 
-		let token_id = format!("{}{}{}", &token_type_id, TOKEN_DELIMETER, num_tokens + 1);
 		token_type.tokens.insert(&token_id);
+		let approved_market_id = token_type.approved_market_id.clone();
 
 		// convert back to versioned
-		versioned_token_type = VersionedTokenType::from(VersionedTokenType::Current(token_type));
+		let versioned_token_type = VersionedTokenType::from(VersionedTokenType::Current(token_type));
 		self.token_type_by_id.insert(&token_type_id, &versioned_token_type);
 
-		let token = self.tokens_mut().internal_mint(token_id.clone(), receiver_id.clone(), Some(VersionedTokenMetadata::from(VersionedTokenMetadata::Current(final_metadata))));
+		self.tokens_mut().internal_mint(token_id.clone(), receiver_id, Some(VersionedTokenMetadata::from(VersionedTokenMetadata::Current(final_metadata))));
 
-    refund_deposit(env::storage_usage() - initial_storage_usage);
+		// approve type's market (if any), so NFT can be listed right away
+		if let Some(approved_market_id) = approved_market_id {
+			self.internal_add_approval(&token_id, &approved_market_id);
+		}
 
-		env::log_str(format!("{}{}", EVENT_JSON, json!({
-			"standard": "nep171",
-			"version": "1.0.0",
-			"event": "nft_mint",
-			"data": [
-			  	{
-					  "owner_id": receiver_id,
-					  "token_ids": [token_id]
-				}
-			]
-		})).as_ref());
-			
-		token
+		// return assembled metadata rather than as stored, so it matches enumeration
+		self.nft_token(token_id).expect(ERR_TOKEN_NOT_FOUND)
 	}
 
-	#[payable]
-	fn nft_batch_mint_type(
-		&mut self,
-		token_type_title: TokenTypeTitle,
-		receiver_ids: Vec<AccountId>
-	) -> Vec<Token>
-	{
-		// Don't allow batch minting for token types with more than one asset because the same random
-		// number seed will be used for all mints
-		let token_type_id = self.token_type_by_title.get(&token_type_title).expect("no type");
-		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("no token");
-		let token_type = versioned_token_type_to_token_type(versioned_token_type);
-		let asset_count = token_type.asset_count;
-		log!(format!("asset_count: {}", asset_count));
-		assert!(asset_count == 1, "batch minting not allowed for token types with more than one asset");
+	/// Edition the next sequential mint of a type will be assigned, i.e. `TokenType::next_edition` skipping reserved editions
+	pub(crate) fn internal_next_sequential_edition(&self, token_type_id: TokenTypeId, token_type: &TokenType) -> u64 {
+		let mut edition = token_type.next_edition();
+		while self.internal_is_edition_reserved(token_type_id, edition) {
+			edition = edition.checked_add(1).unwrap_or_else(|| env::panic_str(ERR_EDITION_OVERFLOW));
+		}
+		edition
+	}
 
-		assert_eq!(env::predecessor_account_id(), self.tokens().owner_id, "Unauthorized");
-		let mut tokens = Vec::new();
+	/// Number of NFTs of a type that sequential mints can still assign, i.e. supply left excluding reserved editions. Stops counting
+	/// at `limit`, so the result is exact only if below it
+	pub(crate) fn internal_available_supply(&self, token_type_id: TokenTypeId, token_type: &TokenType, limit: u64) -> u64 {
+		let max_copies = token_type.metadata.copies.unwrap_or(u64::MAX);
+		let target = limit.min(max_copies.saturating_sub(token_type.num_minted()));
+		let mut available = 0;
+		for edition in token_type.next_edition()..=max_copies {
+			if available >= target {
+				break;
+			}
+			if !self.internal_is_edition_reserved(token_type_id, edition) {
+				available += 1;
+			}
+		}
+		available
+	}
 
-		// Check length of receiver_ids
-		assert!(receiver_ids.len() <= 1000, "receiver_ids must be less than or equal to 1000");
+	/// Whether `edition` of a type has been reserved via `nft_reserve_edition` (whether or not it has since been claimed), so is never
+	/// assigned by sequential mints
+	pub(crate) fn internal_is_edition_reserved(&self, token_type_id: TokenTypeId, edition: u64) -> bool {
+		self.reserved_editions.contains_key(&(token_type_id, edition)) || self.claimed_editions.contains(&(token_type_id, edition))
+	}
 
-		for receiver_id in receiver_ids {
-			tokens.push(self.nft_mint_type(token_type_title.clone(), receiver_id.clone(), None));
+	/// Move a type still stored in `token_type_by_id_v1` into `token_type_by_id` (no-op if already migrated), so it can be minted into
+	/// without waiting for `migrate_token_types`. Returns whether the type was migrated
+	pub(crate) fn internal_migrate_token_type(&mut self, token_type_id: TokenTypeId) -> bool {
+		let token_type_v1 = match self.token_type_by_id_v1.remove(&token_type_id) {
+			Some(token_type_v1) => token_type_v1,
+			None => return false,
+		};
+		assert!(self.token_type_by_id.get(&token_type_id).is_none(), "{}: {}", ERR_TYPE_ALREADY_UPGRADED, token_type_id);
+		self.token_type_by_id.insert(&token_type_id, &VersionedTokenType::Current(TokenType::from(token_type_v1)));
+		true
+	}
+
+	/// Move each asset's sha256 (optional 4th element, already validated by `assert_valid_assets`) into `asset_hashes_by_type`,
+	/// leaving assets of shape [filename, supply_remaining, extra] as expected on mint
+	pub(crate) fn internal_store_asset_hashes(&mut self, token_type_id: TokenTypeId, assets: &mut TokenTypeAssets) {
+		let mut hashes = self.asset_hashes_by_type.get(&token_type_id).unwrap_or_default();
+		let mut updated = false;
+		for asset_detail in assets.iter_mut() {
+			if asset_detail.len() == 4 {
+				let hash = asset_detail.pop().unwrap();
+				hashes.insert(asset_detail[0].clone(), hash.to_lowercase());
+				updated = true;
+			}
+		}
+		if updated {
+			self.asset_hashes_by_type.insert(&token_type_id, &hashes);
 		}
-		tokens
 	}
 
-	#[payable]
-	fn nft_delete_type(
-		&mut self,
-		token_type_title: TokenTypeTitle,
-	) {
-		let initial_storage_usage = env::storage_usage();
-    let owner_id = env::predecessor_account_id();
-		assert_eq!(owner_id.clone(), self.tokens().owner_id, "Unauthorized");
+	/// Panic if `royalty` doesn't pay each recipient of the contract's `min_royalty` at least its minimum (in basis points)
+	pub(crate) fn internal_assert_min_royalty(&self, royalty: &HashMap<AccountId, u32>) {
+		for (account_id, min_bps) in self.min_royalty.iter() {
+			let bps = royalty.get(account_id).copied().unwrap_or(0);
+			require!(
+				bps >= *min_bps,
+				format!("Royalty must pay {} at least {} (basis points). Received {}", account_id, min_bps, bps)
+			);
+		}
+	}
 
-		let token_type_id = self.token_type_by_title.get(&token_type_title).expect("no type");
-		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("no token");
-		let token_type = versioned_token_type_to_token_type(versioned_token_type);
-		
-		// check if there are any tokens (can't delete if there are minted NFTs)
-		let num_tokens = token_type.tokens.len();
-		assert!(num_tokens < 1, "Cannot delete a type that contains tokens (found {} tokens)", num_tokens);
+	/// Panic if type `title` contains `TOKEN_DELIMETER` or the contract's title/edition delimiters
+	pub(crate) fn internal_assert_valid_title(&self, title: &str) {
+		for delimiter in [TOKEN_DELIMETER.to_string(), self.title_delimeter.clone(), self.edition_delimeter.clone()] {
+			require!(
+				delimiter.is_empty() || !title.contains(&delimiter),
+				format!("token_metadata.title must not contain reserved delimiter \"{}\"", delimiter)
+			);
+		}
+	}
 
-		// remove from token_type_by_id
-		self.token_type_by_id.remove(&token_type_id);
-		// remove from token_type_by_title
-		self.token_type_by_title.remove(&token_type_title);
-		// remove from token_type_assets_by_id
-		self.token_type_assets_by_id.remove(&token_type_id);
+	/// Panic if `account_id` already holds the maximum number of NFTs allowed for this type (type owner is exempt)
+	pub(crate) fn internal_assert_holdings_cap(&self, token_type_id: TokenTypeId, token_type: &TokenType, account_id: &AccountId) {
+		if let Some(max_holdings) = token_type.max_holdings_per_account {
+			if account_id != &token_type.owner_id {
+				require!(self.internal_holdings(token_type_id, account_id) < max_holdings, ERR_HOLDINGS_CAP_REACHED);
+			}
+		}
+	}
+
+	/// Number of NFTs of a type currently held by `account_id`.
+	///
+	/// NB: holdings are only tracked for NFTs minted after holdings tracking was introduced
+	pub(crate) fn internal_holdings(&self, token_type_id: TokenTypeId, account_id: &AccountId) -> u64 {
+		self.holdings_by_type.get(&(token_type_id, account_id.clone())).unwrap_or(0)
+	}
+
+	/// Type of `token_id` (whether or not yet migrated from `token_type_by_id_v1`), or `None` if it can't be resolved e.g. legacy token_ids
+	/// not of the form `{token_type_id}:{edition}`
+	pub(crate) fn internal_token_type_for_token(&self, token_id: &str) -> Option<(TokenTypeId, TokenType)> {
+		let token_type_id = try_token_type_id_from_token_id(token_id)?;
+		let token_type = match self.token_type_by_id.get(&token_type_id) {
+			Some(versioned_token_type) => versioned_token_type_to_token_type(versioned_token_type),
+			None => TokenType::from(self.token_type_by_id_v1.get(&token_type_id)?),
+		};
+		Some((token_type_id, token_type))
+	}
+
+	pub(crate) fn internal_increment_holdings(&mut self, token_type_id: TokenTypeId, account_id: &AccountId) {
+		let holdings = self.internal_holdings(token_type_id, account_id);
+		self.holdings_by_type.insert(&(token_type_id, account_id.clone()), &(holdings + 1));
+	}
+
+	pub(crate) fn internal_decrement_holdings(&mut self, token_type_id: TokenTypeId, account_id: &AccountId) {
+		let holdings = self.internal_holdings(token_type_id, account_id);
+		if holdings > 1 {
+			self.holdings_by_type.insert(&(token_type_id, account_id.clone()), &(holdings - 1));
+		} else {
+			self.holdings_by_type.remove(&(token_type_id, account_id.clone()));
+		}
+	}
+}
 
-		let amt_to_refund = if env::storage_usage() > initial_storage_usage { env::storage_usage() - initial_storage_usage } else { initial_storage_usage - env::storage_usage() };
-    refund_deposit(amt_to_refund);
+/// Get token_type_id from a token_id of format {token_type_id}{TOKEN_DELIMETER}{edition} e.g. "42:2"
+pub(crate) fn token_type_id_from_token_id(token_id: &TokenId) -> TokenTypeId {
+	token_id.split(TOKEN_DELIMETER).next().unwrap().parse().expect(ERR_INVALID_TOKEN_ID)
+}
+
+/// As `token_type_id_from_token_id`, but `None` rather than panicking for token_ids not of that format (e.g. legacy ids)
+pub(crate) fn try_token_type_id_from_token_id(token_id: &str) -> Option<TokenTypeId> {
+	token_id.split(TOKEN_DELIMETER).next()?.parse().ok()
+}
+
+/// Panic if type has been frozen via `nft_freeze_type`
+pub(crate) fn assert_not_frozen(token_type: &TokenType) {
+	require!(!token_type.frozen, ERR_TYPE_FROZEN);
+}
+
+/// Panic if `cover_asset` isn't the filename of one of `assets` (e.g. a typo, which would yield a broken cover image)
+pub(crate) fn assert_valid_cover_asset(assets: &TokenTypeAssets, cover_asset: &str) {
+	require!(
+		assets.iter().any(|asset_detail| asset_detail[0] == cover_asset),
+		format!("cover_asset '{}' must be the filename of one of the type's assets", cover_asset)
+	);
+}
+
+/// Panic if any asset isn't of shape [filename, supply_remaining, extra] or [filename, supply_remaining, extra, sha256]. Returns total supply of the assets
+pub(crate) fn assert_valid_assets(assets: &TokenTypeAssets) -> u64 {
+	let mut total_supply = 0 as u64;
+	for (idx, asset_detail) in assets.iter().enumerate() { // TODO: may want to ultimately verify each of these elements on Spearmint instead to save on gas costs (also so we don't hit gas limit... FYI 10,000 is fine, but 50,000+ becomes problematic)
+		// verify shape: [filename, supply_remaining, extra, sha256 (optional)] ("extra" should be empty string if no "extra" file is available for this asset)
+		assert!(asset_detail.len() == 3 || asset_detail.len() == 4, "Asset at index {} must have 3 elements [filename, supply, extra] (if there is no 'extra'/json file available for this asset, 3rd element should be empty string), plus optionally a 4th (sha256 of the asset). Received {} elements", idx, asset_detail.len());
+		if let Some(hash) = asset_detail.get(3) {
+			assert_valid_asset_sha256(idx, hash);
+		}
+		// verify asset filename exists
+		assert!(!asset_detail[0].is_empty(), "Asset at index {} must provide a filename", idx);
+		let supply_remaining: u64 = asset_detail[1].parse().unwrap_or_else(|_| env::panic_str(&format!("Asset at index {} has invalid supply '{}' (must be a whole number)", idx, asset_detail[1])));
+		// an asset is only removed once minted from, so an asset with no supply would still be minted once
+		assert!(supply_remaining > 0, "Asset at index {} must have a supply greater than 0", idx);
+		// tally total_supply to verify against metadata.copies
+		total_supply = total_supply.checked_add(supply_remaining).unwrap_or_else(|| env::panic_str(ERR_TOTAL_SUPPLY_OVERFLOW));
 	}
-}
\ No newline at end of file
+	total_supply
+}
+
+/// Panics unless `hash` (optional 4th element of the `AssetDetail` at `idx`) is a hex-encoded sha256
+pub(crate) fn assert_valid_asset_sha256(idx: usize, hash: &str) {
+	// sha256 is 32 bytes, i.e. 64 hex characters
+	require!(
+		hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()),
+		format!("Asset at index {} has invalid sha256 '{}' (must be 32 bytes, hex-encoded)", idx, hash)
+	);
+}
@@ -6,7 +6,13 @@ pub trait NonFungibleTokenRoyalty {
   //calculates the payout for a token given the passed in balance. This is a view method
   fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout;
 
+  //calculates the payout for a primary sale of a type/series (no NFT needs to exist yet), with the type owner as seller. This is a view method
+  fn nft_type_payout(&self, token_type_title: TokenTypeTitle, balance: U128, max_len_payout: u32) -> Payout;
+
   //transfers the token to the receiver ID and returns the payout object that should be payed given the passed in balance. 
+  //if `memo` is `LAZY_MINT_MEMO_PREFIX` followed by `TypeMintArgs` JSON, mints the next NFT of the type instead (lazy mint); any other memo is a plain transfer memo.
+  //lazy mints may only be made by the type's `approved_market_id` (or the contract owner), with storage paid from the attached deposit
+  //`max_len_payout` defaults to `MAX_ROYALTY_RECIPIENTS` (the most royalty recipients a type can have) if not provided
   fn nft_transfer_payout(
     &mut self,
     receiver_id: AccountId,
@@ -16,6 +22,9 @@ pub trait NonFungibleTokenRoyalty {
     balance: Option<U128>,
     max_len_payout: Option<u32>,
   ) -> Option<Payout>;
+
+  //denominator of royalty percentages (`ROYALTY_DENOMINATOR`), e.g. 10000 for basis points. This is a view method
+  fn nft_royalty_denominator(&self) -> u32;
 } 
 
 #[near_bindgen]
@@ -23,47 +32,27 @@ impl NonFungibleTokenRoyalty for Contract {
   	//calculates the payout for a token given the passed in balance. This is a view method
 	fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout {
 		//get the token object
-		let token = self.nft_token(token_id.clone()).expect("no token");
-
-		//get the owner of the token
-		let owner_id = token.owner_id;
-		//keep track of the total perpetual royalties
-		let mut total_perpetual = 0;
-		//get the u128 version of the passed in balance (which was U128 before)
-		let balance_u128 = u128::from(balance);
-		//keep track of the payout object to send back
-		let mut payout_object = Payout {
-				payout: HashMap::new()
-		};
+		let token = self.nft_token(token_id.clone()).expect(ERR_TOKEN_NOT_FOUND);
+
 		//get the royalty object from token
 		let mut token_id_iter = token_id.split(TOKEN_DELIMETER);
 		let token_type_id = token_id_iter.next().unwrap().parse().unwrap();
-		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("no type");
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
 		let token_type = versioned_token_type_to_token_type(versioned_token_type);
-		let royalty = token_type.royalty;
-
-		//make sure we're not paying out to too many people (GAS limits this)
-		assert!(royalty.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
-
-		//go through each key and value in the royalty object
-		for (k, v) in royalty.iter() {
-			//get the key
-			let key = k.clone();
-			//only insert into the payout if the key isn't the token owner (we add their payout at the end)
-			if key != owner_id {
-				payout_object.payout.insert(key, royalty_to_payout(*v, balance_u128));
-				total_perpetual += *v;
-			}
-		}
 
-		// payout to previous owner who gets 100% - total perpetual royalties
-		let owner_payout = royalty_to_payout(10000 - total_perpetual, balance_u128);
-		if u128::from(owner_payout) > 0 {
-			payout_object.payout.insert(owner_id, owner_payout);
-		}
+		//the owner of the token is the seller
+		royalty_payout(&token_type.royalty, token.owner_id, balance, max_len_payout)
+	}
+
+	//calculates the payout for a primary sale of a type given the passed in balance, with the type owner as seller. This is a view method
+	fn nft_type_payout(&self, token_type_title: TokenTypeTitle, balance: U128, max_len_payout: u32) -> Payout {
+		//get the royalty object from type
+		let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+		let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+		let token_type = versioned_token_type_to_token_type(versioned_token_type);
 
-		//return the payout object
-		payout_object
+		//the owner of the type is the seller
+		royalty_payout(&token_type.royalty, token_type.owner_id, balance, max_len_payout)
 	}
 
 	/// CUSTOM royalties payout
@@ -78,64 +67,87 @@ impl NonFungibleTokenRoyalty for Contract {
 		max_len_payout: Option<u32>,
 	) -> Option<Payout> {
 
-		let type_mint_args = memo.clone();
-		let previous_token = if let Some(type_mint_args) = type_mint_args {
+		let type_mint_args = memo.as_ref().and_then(|memo| memo.strip_prefix(LAZY_MINT_MEMO_PREFIX)).map(String::from);
+		// `owner_id` is the seller: for a lazy mint (primary sale) this is the type owner, otherwise the previous token owner
+		let (token_id, owner_id) = if let Some(type_mint_args) = type_mint_args {
+			let initial_storage_usage = env::storage_usage();
 			let TypeMintArgs{token_type_title, receiver_id} = near_sdk::serde_json::from_str(&type_mint_args).expect("invalid TypeMintArgs");
-			self.nft_mint_type(token_type_title, receiver_id.clone(), None)
+			let token_type_id = self.token_type_by_title.get(&token_type_title).expect(ERR_NO_TYPE);
+			self.internal_migrate_token_type(token_type_id);
+			let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+			let token_type = versioned_token_type_to_token_type(versioned_token_type);
+			// only the type's approved market (or the contract owner) may sell its unminted editions
+			let predecessor_account_id = env::predecessor_account_id();
+			require!(
+				token_type.approved_market_id.as_ref() == Some(&predecessor_account_id) || predecessor_account_id == self.tokens().owner_id,
+				ERR_UNAUTHORIZED
+			);
+			// a market sale is a public mint, so is paused while the type is disabled
+			require!(token_type.enabled || predecessor_account_id == self.tokens().owner_id, ERR_TYPE_DISABLED);
+			let minted_token = self.internal_mint_type(token_type_id, receiver_id.clone(), None);
+			// storage is paid from the deposit attached by the market (buyer's deposit in excess of price)
+			refund_deposit(env::storage_usage() - initial_storage_usage);
+			emit_nft_mint(&receiver_id, &[minted_token.token_id.clone()]);
+			(minted_token.token_id, token_type.owner_id)
 		} else {
-			let prev_token = self.nft_token(token_id.clone()).expect("no token");
+			let prev_token = self.nft_token(token_id.clone()).expect(ERR_TOKEN_NOT_FOUND);
 			self.nft_transfer(receiver_id.clone(), token_id.clone(), Some(approval_id), memo);
-			prev_token
+			(token_id, prev_token.owner_id)
 		};
 
 		// compute payouts based on balance option
-		let owner_id = previous_token.owner_id;
-		let payout_struct = if let Some(balance) = balance {
-				let complete_royalty = 10_000u128;
-				let balance_piece = u128::from(balance) / complete_royalty;
-				let mut total_royalty_percentage = 0;
-				let mut payout_struct: Payout = Payout{
-					payout: HashMap::new()
-				};
-				let mut token_id_iter = token_id.split(TOKEN_DELIMETER);
-				let token_type_id = token_id_iter.next().unwrap().parse().unwrap();
-				let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect("no type");
-				let token_type = versioned_token_type_to_token_type(versioned_token_type);
-				let royalty = token_type.royalty;
-
-				if let Some(max_len_payout) = max_len_payout {
-						assert!(royalty.len() as u32 <= max_len_payout, "exceeds max_len_payout");
-				}
-				for (k, v) in royalty.iter() {
-						let key = k.clone();
-						// skip seller and payout once at end
-						if key != owner_id {
-								payout_struct.payout.insert(key, U128(*v as u128 * balance_piece));
-								total_royalty_percentage += *v;
-						}
-				}
-				// payout to seller
-				let seller_payout = (complete_royalty - total_royalty_percentage as u128) * balance_piece;
-				if seller_payout > 0 {
-					payout_struct.payout.insert(owner_id.clone(), U128(seller_payout));
-				}
-				Some(payout_struct)
-		} else {
-				None
-		};
+		let payout_struct = balance.map(|balance| {
+			let token_type_id = token_type_id_from_token_id(&token_id);
+			let versioned_token_type = self.token_type_by_id.get(&token_type_id).expect(ERR_NO_TYPE);
+			let token_type = versioned_token_type_to_token_type(versioned_token_type);
+			royalty_payout(&token_type.royalty, owner_id, balance, max_len_payout.unwrap_or(MAX_ROYALTY_RECIPIENTS))
+		});
 
-		env::log_str(format!("{}{}", EVENT_JSON, json!({
-			"standard": "nep171",
-			"version": "1.0.0",
-			"event": "nft_transfer",
-			"data": [
-				{
-					"old_owner_id": owner_id, "new_owner_id": receiver_id, "token_ids": [token_id]
-				}
-			]
-		})).as_ref());
+		// the `nft_mint` / `nft_transfer` event has already been logged
 
     payout_struct
 	}
 
-}
\ No newline at end of file
+	fn nft_royalty_denominator(&self) -> u32 {
+		ROYALTY_DENOMINATOR
+	}
+}
+
+//splits the passed in balance between royalty recipients, with the seller getting the rest (100% - total perpetual royalties, plus rounding dust)
+fn royalty_payout(royalty: &HashMap<AccountId, u32>, owner_id: AccountId, balance: U128, max_len_payout: u32) -> Payout {
+	//keep track of the total paid out in perpetual royalties
+	let mut total_royalties: u128 = 0;
+	//get the u128 version of the passed in balance (which was U128 before)
+	let balance_u128 = u128::from(balance);
+	//keep track of the payout object to send back
+	let mut payout_object = Payout {
+			payout: HashMap::new()
+	};
+
+	//make sure we're not paying out to too many people (GAS limits this)
+	assert!(
+		royalty.len() as u32 <= max_len_payout,
+		"Royalty has {} receivers, which exceeds max_len_payout of {}", royalty.len(), max_len_payout
+	);
+
+	//go through each key and value in the royalty object
+	for (k, v) in royalty.iter() {
+		//get the key
+		let key = k.clone();
+		//only insert into the payout if the key isn't the seller (we add their payout at the end)
+		if key != owner_id {
+			let amount = royalty_to_payout(*v, balance_u128);
+			total_royalties += u128::from(amount);
+			payout_object.payout.insert(key, amount);
+		}
+	}
+
+	// payout to seller who gets the rest, i.e. 100% - total perpetual royalties plus any rounding dust, so payouts sum to exactly `balance`
+	let owner_payout = balance_u128 - total_royalties;
+	if owner_payout > 0 {
+		payout_object.payout.insert(owner_id, U128(owner_payout));
+	}
+
+	//return the payout object
+	payout_object
+}
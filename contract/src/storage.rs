@@ -0,0 +1,152 @@
+use crate::*;
+
+use near_sdk::{assert_one_yocto, env, require, AccountId, Balance, Promise};
+use near_sdk::json_types::U128;
+
+/// bytes charged by the protocol for each storage record on top of its key & value (`storage_num_extra_bytes_record` in NEAR's
+/// runtime config)
+pub const STORAGE_RECORD_OVERHEAD_BYTES: u64 = 40;
+
+/// storage used by an account's entry in `Contract.storage_deposits` (account ID of max length 64 + 4 bytes Borsh length prefix, u128
+/// balance, 1 byte collection prefix & storage record overhead). Covered by the registration deposit (`storage_balance_bounds().min`)
+pub const STORAGE_BALANCE_ENTRY_BYTES: u64 = 64 + 4 + 16 + 1 + STORAGE_RECORD_OVERHEAD_BYTES;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+	pub total: U128,
+	pub available: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+	pub min: U128,
+	pub max: Option<U128>,
+}
+
+/// Minimal storage management standard (NEP-145), letting dApps pre-pay storage for accounts that will mint.
+/// Storage for `nft_mint_type` is drawn from the receiver's balance, and for `nft_public_mint` from the caller's balance, if
+/// their available balance covers it (otherwise the attached deposit is charged as usual).
+///
+/// [storage management standard]: https://nomicon.io/Standards/StorageManagement
+pub trait StorageManagement {
+	/// Deposit storage balance for `account_id` (defaults to caller). If `registration_only` is true, only the minimum
+	/// deposit is kept (all of the deposit is refunded if already registered)
+	fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>) -> StorageBalance;
+
+	/// Withdraw `amount` (defaults to all) of caller's available storage balance. Requires 1 yoctoⓃ attached
+	fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance;
+
+	/// Storage balance of `account_id`, or `None` if not registered
+	fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance>;
+
+	/// Min deposit (registration) and max deposit (none)
+	fn storage_balance_bounds(&self) -> StorageBalanceBounds;
+}
+
+#[near_bindgen]
+impl StorageManagement for Contract {
+	#[payable]
+	fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>) -> StorageBalance {
+		let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+		let attached_deposit = env::attached_deposit();
+		let min_balance = storage_balance_min();
+
+		let previous_total = self.storage_deposits.get(&account_id);
+		let total = match previous_total {
+			Some(total) if registration_only.unwrap_or(false) => {
+				// already registered; nothing to do
+				if attached_deposit > 0 {
+					Promise::new(env::predecessor_account_id()).transfer(attached_deposit);
+				}
+				total
+			}
+			Some(total) => total + attached_deposit,
+			None => {
+				require!(
+					attached_deposit >= min_balance,
+					format!("Must attach at least {} yoctoNEAR to register", min_balance)
+				);
+				if registration_only.unwrap_or(false) {
+					let refund = attached_deposit - min_balance;
+					if refund > 0 {
+						Promise::new(env::predecessor_account_id()).transfer(refund);
+					}
+					min_balance
+				} else {
+					attached_deposit
+				}
+			}
+		};
+		self.storage_deposits.insert(&account_id, &total);
+		self.storage_deposits_total = self.storage_deposits_total + total - previous_total.unwrap_or(0);
+
+		storage_balance(total)
+	}
+
+	#[payable]
+	fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+		assert_one_yocto();
+		let account_id = env::predecessor_account_id();
+		let total = self.storage_deposits.get(&account_id).expect("account is not registered");
+		let available = total - storage_balance_min();
+		let amount: Balance = amount.map(|amount| amount.into()).unwrap_or(available);
+		require!(amount <= available, format!("Cannot withdraw more than available balance of {} yoctoNEAR", available));
+
+		let total = total - amount;
+		self.storage_deposits.insert(&account_id, &total);
+		self.storage_deposits_total -= amount;
+		if amount > 0 {
+			Promise::new(account_id).transfer(amount);
+		}
+
+		storage_balance(total)
+	}
+
+	fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+		self.storage_deposits.get(&account_id).map(storage_balance)
+	}
+
+	fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+		StorageBalanceBounds {
+			min: storage_balance_min().into(),
+			max: None,
+		}
+	}
+}
+
+impl Contract {
+	/// Pay for `storage_used` bytes from `account_id`'s available storage balance, if registered and it covers the cost.
+	/// Returns false (leaving the balance untouched) otherwise, in which case the caller should charge the attached deposit
+	pub(crate) fn internal_draw_storage_balance(&mut self, account_id: &AccountId, storage_used: u64) -> bool {
+		let total = match self.storage_deposits.get(account_id) {
+			Some(total) => total,
+			None => return false,
+		};
+		let storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
+		if total - storage_balance_min() < storage_cost {
+			return false;
+		}
+		self.storage_deposits.insert(account_id, &(total - storage_cost));
+		// drawn balance now pays for storage staking
+		self.storage_deposits_total -= storage_cost;
+		true
+	}
+}
+
+/// storage used by a single record with a key of `key_len` & value of `value_len` bytes
+pub(crate) fn storage_record_bytes(key_len: usize, value_len: usize) -> u64 {
+	(key_len + value_len) as u64 + STORAGE_RECORD_OVERHEAD_BYTES
+}
+
+fn storage_balance_min() -> Balance {
+	env::storage_byte_cost() * Balance::from(STORAGE_BALANCE_ENTRY_BYTES)
+}
+
+fn storage_balance(total: Balance) -> StorageBalance {
+	StorageBalance {
+		total: total.into(),
+		available: (total - storage_balance_min()).into(),
+	}
+}
@@ -1,5 +1,6 @@
-use near_sdk::{env, require, AccountId, Balance, CryptoHash, Promise};
+use near_sdk::{env, require, serde_json::json, AccountId, Balance, CryptoHash, Promise};
 use near_sdk::json_types::{U128};
+use crate::{NFTContractMetadata, TokenId, CUSTOM_EVENT_STANDARD, CUSTOM_EVENT_VERSION, EVENT_JSON};
 use std::collections::HashMap;
 use std::mem::size_of;
 
@@ -43,6 +44,21 @@ pub fn refund_deposit(storage_used: u64) {
     }
 }
 
+/// Settle storage for a method that may have either used or freed storage since `initial_storage_usage`: charges for (refunding
+/// excess deposit) bytes used, as `refund_deposit`, or refunds the attached deposit plus the cost of bytes freed to the predecessor
+pub(crate) fn refund_or_reward_storage(initial_storage_usage: u64) {
+    let storage_usage = env::storage_usage();
+    if storage_usage >= initial_storage_usage {
+        refund_deposit(storage_usage - initial_storage_usage);
+        return;
+    }
+
+    let refund = env::attached_deposit() + env::storage_byte_cost() * Balance::from(initial_storage_usage - storage_usage);
+    if refund > 1 {
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+    }
+}
+
 pub fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     let mut hash = CryptoHash::default();
     hash.copy_from_slice(&env::sha256(account_id.as_bytes()));
@@ -54,15 +70,107 @@ pub(crate) fn assert_at_least_one_yocto() {
     require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR")
 }
 
+/// Assert that royalty percentages (in basis points) sum to no more than 100% and that there are not too many recipients to pay out
+pub(crate) fn assert_valid_royalty(royalty: &HashMap<AccountId, u32>) {
+    require!(
+        royalty.len() as u32 <= crate::MAX_ROYALTY_RECIPIENTS,
+        format!("Royalty cannot have more than {} recipients", crate::MAX_ROYALTY_RECIPIENTS)
+    );
+    let total: u32 = royalty.values().sum();
+    require!(
+        total <= crate::ROYALTY_DENOMINATOR,
+        format!("Royalty must not exceed {} (100%). Received {}", crate::ROYALTY_DENOMINATOR, total)
+    );
+}
+
+/// Log a custom event listing the contract metadata fields changed from `old` to `new` (nothing is logged if none changed), with schema:
+/// `EVENT_JSON:{"standard":"nft_series","version":"1.0.0","event":"contract_metadata_update","data":[{"fields":["base_uri"]}]}`
+/// Indexers should re-fetch `nft_metadata` (e.g. to re-resolve token media URLs against the new `base_uri`)
+pub(crate) fn log_contract_metadata_update(old: &NFTContractMetadata, new: &NFTContractMetadata) {
+    let mut fields = Vec::new();
+    if old.name != new.name { fields.push("name"); }
+    if old.symbol != new.symbol { fields.push("symbol"); }
+    if old.icon != new.icon { fields.push("icon"); }
+    if old.base_uri != new.base_uri { fields.push("base_uri"); }
+    if old.reference != new.reference { fields.push("reference"); }
+    if old.reference_hash != new.reference_hash { fields.push("reference_hash"); }
+    if old.extra != new.extra { fields.push("extra"); }
+    if fields.is_empty() {
+        return;
+    }
+
+    env::log_str(format!("{}{}", EVENT_JSON, json!({
+        "standard": CUSTOM_EVENT_STANDARD,
+        "version": CUSTOM_EVENT_VERSION,
+        "event": "contract_metadata_update",
+        "data": [
+            {
+                "fields": fields,
+            }
+        ]
+    })).as_ref());
+}
+
+/// Log a NEP-171 `nft_mint` event, with schema:
+/// `EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"alice.near","token_ids":["1:1"]}]}`
+pub(crate) fn emit_nft_mint(owner_id: &AccountId, token_ids: &[TokenId]) {
+    emit_nft_mint_batch(&[(owner_id.clone(), token_ids.to_vec())]);
+}
+
+/// Log a single NEP-171 `nft_mint` event covering several owners, with an entry (as in `emit_nft_mint`) per owner
+pub(crate) fn emit_nft_mint_batch(token_ids_by_owner: &[(AccountId, Vec<TokenId>)]) {
+    let data: Vec<_> = token_ids_by_owner.iter().map(|(owner_id, token_ids)| json!({
+        "owner_id": owner_id,
+        "token_ids": token_ids,
+    })).collect();
+    env::log_str(format!("{}{}", EVENT_JSON, json!({
+        "standard": "nep171",
+        "version": "1.0.0",
+        "event": "nft_mint",
+        "data": data
+    })).as_ref());
+}
+
+/// Log a NEP-171 `nft_transfer` event, with schema (`authorized_id` & `memo` are omitted if `None`):
+/// `EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","token_ids":["1:1"],"authorized_id":"market.near","memo":"..."}]}`
+pub(crate) fn emit_nft_transfer(
+    old_owner_id: &AccountId,
+    new_owner_id: &AccountId,
+    token_ids: &[TokenId],
+    authorized_id: Option<&AccountId>,
+    memo: Option<&str>,
+) {
+    let mut data = json!({
+        "old_owner_id": old_owner_id,
+        "new_owner_id": new_owner_id,
+        "token_ids": token_ids,
+    });
+    if let Some(authorized_id) = authorized_id {
+        data["authorized_id"] = json!(authorized_id);
+    }
+    if let Some(memo) = memo {
+        data["memo"] = json!(memo);
+    }
+    env::log_str(format!("{}{}", EVENT_JSON, json!({
+        "standard": "nep171",
+        "version": "1.0.0",
+        "event": "nft_transfer",
+        "data": [data]
+    })).as_ref());
+}
+
 /// convert the royalty percentage and amount to pay into a payout (U128)
 pub(crate) fn royalty_to_payout(royalty_percentage: u32, amount_to_pay: Balance) -> U128 {
-    U128(royalty_percentage as u128 * amount_to_pay / 10_000u128)
+    U128(royalty_percentage as u128 * amount_to_pay / crate::ROYALTY_DENOMINATOR as u128)
 }
 
-pub(crate) fn random_u128() -> u128 {
+/// random number derived from block random seed mixed with `salt`, so that several calls within the same
+/// block (e.g. batch mints) each yield a different number when given different salts
+pub(crate) fn random_u128(salt: &[u8]) -> u128 {
     let random_seed = env::random_seed(); // len 32
+    let salted_seed = env::sha256(&[random_seed.as_slice(), salt].concat()); // len 32
     // using first 16 bytes (doesn't affect randomness)
-    as_u128(random_seed.get(..16).unwrap())
+    as_u128(salted_seed.get(..16).unwrap())
 }
 
 fn as_u128(arr: &[u8]) -> u128 {
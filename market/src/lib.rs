@@ -26,6 +26,8 @@ mod sale_views;
 const NO_DEPOSIT: Balance = 0;
 const STORAGE_PER_SALE: u128 = 1000 * STORAGE_PRICE_PER_BYTE;
 static DELIMETER: &str = "||";
+/// prefix of `memo` passed to `nft_transfer_payout` for series (lazy mint) purchases, followed by `TypeMintArgs` JSON
+static LAZY_MINT_MEMO_PREFIX: &str = "LAZY_MINT:";
 
 pub type SaleConditions = HashMap<FungibleTokenId, U128>;
 pub type TokenId = String;
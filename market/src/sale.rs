@@ -155,11 +155,19 @@ impl Contract {
             nft_transfer_deposit = 1
         }
 
+        // for series sales `msg` holds `TypeMintArgs` JSON, which the NFT contract lazy mints when prefixed;
+        // for any other sale it is passed through as a plain transfer memo
+        let memo = if sale.is_series.is_some() {
+            msg.map(|msg| format!("{}{}", LAZY_MINT_MEMO_PREFIX, msg))
+        } else {
+            msg
+        };
+
         ext_contract::nft_transfer_payout(
             buyer_id.clone(),
             token_id,
             sale.approval_id,
-            msg,
+            memo,
             price,
             nft_contract_id,
             // price paid remains with contract (excess deposit for storage cost of series lazy mint)
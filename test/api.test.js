@@ -1082,4 +1082,5907 @@ describe("NFT Series", function () {
     }
     assert.deepEqual(res.payout, expected);
   });
+
+  // nft_public_mint
+  it("should allow anyone to public mint a priced type, paying price, mint fee & storage", async function () {
+    const title = "public-mint-" + Date.now();
+    const price = parseNearAmount("0.5");
+    const mintFee = parseNearAmount("0.1");
+    const assets = [["public.jpg", "10", ""]];
+
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {},
+        cover_asset: assets[0][0],
+        price,
+      },
+      parseNearAmount("0.1")
+    );
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_mint_fee",
+      args: { mint_fee: mintFee },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "get_mint_fee"),
+      mintFee
+    );
+
+    // price + mint fee without storage is not enough
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_public_mint",
+        args: { token_type_title: title },
+        gas,
+        attachedDeposit: new BN(price).add(new BN(mintFee)).toString(),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Must attach/.test(e.toString()));
+    }
+
+    const aliceBalanceBefore = (await getAccountBalance(aliceId)).total;
+    const ownerBalanceBefore = (await getAccountBalance(contractId)).total;
+
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_public_mint",
+      args: { token_type_title: title },
+      gas,
+      attachedDeposit: parseNearAmount("1"),
+    });
+
+    const aliceBalanceAfter = (await getAccountBalance(aliceId)).total;
+    const ownerBalanceAfter = (await getAccountBalance(contractId)).total;
+    const priceAndFee = new BN(price).add(new BN(mintFee));
+
+    // alice paid price + fee + storage (+ gas), and was refunded the remainder of her 1 N deposit
+    const alicePaid = new BN(aliceBalanceBefore).sub(new BN(aliceBalanceAfter));
+    assert(alicePaid.gte(priceAndFee));
+    assert(alicePaid.lt(priceAndFee.add(new BN(parseNearAmount("0.1")))));
+    // type owner (price) & contract owner (fee) are the same account on this contract; storage is also retained
+    assert(
+      new BN(ownerBalanceAfter).sub(new BN(ownerBalanceBefore)).gte(priceAndFee)
+    );
+
+    const tokens = await contractAccount.viewFunction(
+      contractId,
+      "nft_tokens_by_type",
+      { token_type_title: title }
+    );
+    assert.strictEqual(tokens.length, 1);
+    assert.strictEqual(tokens[0].owner_id, aliceId);
+
+    // reset mint fee for future tests
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_mint_fee",
+      args: { mint_fee: null },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+  });
+
+  it("should NOT allow public minting of a type without a price", async function () {
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_public_mint",
+        args: { token_type_title: token_type_title_non_gen },
+        gas,
+        attachedDeposit: parseNearAmount("1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/type is not available for public minting/.test(e.toString()));
+    }
+  });
+
+
+  // nft_update_royalty
+  it("should allow the owner to update only the royalty recipients of a type, reflected immediately in nft_payout", async function () {
+    const token_type_original = await contractAccount.viewFunction(
+      contractId,
+      "nft_get_type",
+      { token_type_title: token_type_title_semi_gen }
+    );
+    const [{ token_id: semi_gen_token_id }] = await contractAccount.viewFunction(
+      contractId,
+      "nft_tokens_by_type",
+      { token_type_title: token_type_title_semi_gen, limit: 1 }
+    );
+
+    const updatedRoyalty = { [aliceId]: 500, [marketId]: 1500 };
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_update_royalty",
+      args: {
+        token_type_title: token_type_title_semi_gen,
+        royalty: updatedRoyalty,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const token_type_updated = await contractAccount.viewFunction(
+      contractId,
+      "nft_get_type",
+      { token_type_title: token_type_title_semi_gen }
+    );
+    assert.deepEqual(token_type_updated.royalty, updatedRoyalty);
+    assert.deepEqual(token_type_updated.metadata, token_type_original.metadata);
+
+    const { payout } = await contractAccount.viewFunction(contractId, "nft_payout", {
+      token_id: semi_gen_token_id,
+      balance: "10000",
+      max_len_payout: 9,
+    });
+    assert.deepEqual(payout, {
+      [aliceId]: "500",
+      [marketId]: "1500",
+      [contractId]: "8000",
+    });
+
+    // revert to original value for future tests
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_update_royalty",
+      args: {
+        token_type_title: token_type_title_semi_gen,
+        royalty: token_type_original.royalty,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+  });
+
+  it("should NOT allow a royalty update exceeding 100% or too many recipients", async function () {
+    const invalidRoyalties = [{ [aliceId]: 6000, [bobId]: 4001 }, {}];
+    for (let i = 0; i < 11; i++) {
+      invalidRoyalties[1][`${i}-${aliceId}`] = 1;
+    }
+    for (const royalty of invalidRoyalties) {
+      try {
+        await contractAccount.functionCall({
+          contractId,
+          methodName: "nft_update_royalty",
+          args: { token_type_title: token_type_title_semi_gen, royalty },
+          gas,
+          attachedDeposit: parseNearAmount("0.1"),
+        });
+        assert(false);
+      } catch (e) {
+        assert(/Royalty/.test(e.toString()));
+      }
+    }
+  });
+
+
+  // capped edition display
+  it("should display editions against original copies before and after capping, and report both copies values", async function () {
+    const title = "capped-display-" + Date.now();
+    const assets = [["capped.jpg", "5", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets,
+        royalty: {},
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("0.1")
+    );
+    for (let i = 0; i < 2; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id: contractId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+
+    const [TOKEN_DELIMETER, TITLE_DELIMETER, EDITION_DELIMETER] =
+      await contractAccount.viewFunction(contractId, "nft_get_type_format");
+    const getTitles = async () =>
+      (
+        await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+          token_type_title: title,
+        })
+      ).map(({ metadata }) => metadata.title);
+    const expectedTitles = [1, 2].map(
+      (edition) => `${title}${TITLE_DELIMETER}${edition}${EDITION_DELIMETER}5`
+    );
+
+    // pre-cap
+    assert.deepEqual(await getTitles(), expectedTitles);
+    let token_type = await contractAccount.viewFunction(contractId, "nft_get_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(token_type.metadata.copies, 5);
+    assert.strictEqual(token_type.original_copies, 5);
+    assert.strictEqual(token_type.capped, false);
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_cap_copies",
+      args: { token_type_title: title },
+      gas,
+    });
+
+    // post-cap display is unchanged
+    assert.deepEqual(await getTitles(), expectedTitles);
+    token_type = await contractAccount.viewFunction(contractId, "nft_get_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(token_type.metadata.copies, 2);
+    assert.strictEqual(token_type.original_copies, 5);
+    assert.strictEqual(token_type.capped, true);
+  });
+
+
+  // max_holdings_per_account
+  it("should NOT allow an account to hold more editions of a type than its holdings cap", async function () {
+    const title = "holdings-cap-" + Date.now();
+    const assets = [["holdings.jpg", "5", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets,
+        royalty: {},
+        cover_asset: assets[0][0],
+        max_holdings_per_account: 2,
+      },
+      parseNearAmount("0.1")
+    );
+
+    // type owner is exempt from the cap
+    for (let i = 0; i < 3; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id: contractId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+    const token_ids = (
+      await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+        token_type_title: title,
+      })
+    ).map(({ token_id }) => token_id);
+    assert.strictEqual(token_ids.length, 3);
+
+    for (const token_id of token_ids.slice(0, 2)) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_transfer",
+        args: { receiver_id: aliceId, token_id },
+        gas,
+        attachedDeposit: "1",
+      });
+    }
+
+    // third edition via transfer
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_transfer",
+        args: { receiver_id: aliceId, token_id: token_ids[2] },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Holdings cap reached./.test(e.toString()));
+    }
+
+    // third edition via mint
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id: aliceId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Holdings cap reached./.test(e.toString()));
+    }
+
+    const { owner_id } = await contractAccount.viewFunction(contractId, "nft_token", {
+      token_id: token_ids[2],
+    });
+    assert.strictEqual(owner_id, contractId);
+  });
+
+
+  // type reference & reference_hash
+  it("should validate and surface a type's reference and reference_hash", async function () {
+    const title = "type-reference-" + Date.now();
+    const assets = [["reference.jpg", "1", ""]];
+    const args = {
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies: 1,
+        reference: "https://example.com/series.json",
+        reference_hash: Buffer.alloc(31, 1).toString("base64"),
+      },
+      assets,
+      royalty: {},
+      cover_asset: assets[0][0],
+    };
+
+    try {
+      await testUtils.createType(contractAccount, contractId, args, parseNearAmount("0.1"));
+      assert(false);
+    } catch (e) {
+      assert(/Hash has to be 32 bytes/.test(e.toString()));
+    }
+
+    args.metadata.reference_hash = Buffer.alloc(32, 1).toString("base64");
+    await testUtils.createType(contractAccount, contractId, args, parseNearAmount("0.1"));
+
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(token_type.metadata.reference, args.metadata.reference);
+    assert.strictEqual(token_type.metadata.reference_hash, args.metadata.reference_hash);
+  });
+
+
+  // nft_tokens_in_type_range
+  it("should return only a type's tokens from a token_id range query, respecting adjacent type ids (1 vs 10)", async function () {
+    const [{ token_id: non_gen_token_id }] = await contractAccount.viewFunction(
+      contractId,
+      "nft_tokens_by_type",
+      { token_type_title: token_type_title_non_gen, limit: 1 }
+    );
+    assert.strictEqual(non_gen_token_id.split(TOKEN_DELIMETER)[0], "1");
+
+    // create & mint into single-copy types until type 10 exists
+    let type_10_title;
+    for (let i = 0; i < 10 && !type_10_title; i++) {
+      const title = "range-" + i + "-" + Date.now();
+      const assets = [["range.jpg", "1", ""]];
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies: 1,
+          },
+          assets,
+          royalty: {},
+          cover_asset: assets[0][0],
+        },
+        parseNearAmount("0.1")
+      );
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id: contractId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      const [{ token_id }] = await contractAccount.viewFunction(
+        contractId,
+        "nft_tokens_by_type",
+        { token_type_title: title }
+      );
+      if (token_id.split(TOKEN_DELIMETER)[0] === "10") type_10_title = title;
+    }
+    assert(type_10_title);
+
+    const getIds = async (methodName, token_type_title) =>
+      (
+        await contractAccount.viewFunction(contractId, methodName, {
+          token_type_title,
+        })
+      )
+        .map(({ token_id }) => token_id)
+        .sort();
+
+    const type1Ids = await getIds("nft_tokens_in_type_range", token_type_title_non_gen);
+    assert.deepEqual(type1Ids, await getIds("nft_tokens_by_type", token_type_title_non_gen));
+    assert(type1Ids.every((id) => id.startsWith("1" + TOKEN_DELIMETER)));
+
+    assert.deepEqual(await getIds("nft_tokens_in_type_range", type_10_title), [
+      "10" + TOKEN_DELIMETER + "1",
+    ]);
+
+    // pagination within range
+    const [second] = await contractAccount.viewFunction(
+      contractId,
+      "nft_tokens_in_type_range",
+      { token_type_title: token_type_title_non_gen, from_index: "1", limit: 1 }
+    );
+    assert(second.token_id.startsWith("1" + TOKEN_DELIMETER));
+  });
+
+
+  // nft_approve_batch
+  it("should allow an owner to approve an account on multiple tokens in one call", async function () {
+    const title = "approve-batch-" + Date.now();
+    const assets = [["approve.jpg", "5", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets,
+        royalty: {},
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("0.1")
+    );
+    for (let i = 0; i < 5; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id: aliceId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+    const token_ids = (
+      await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+        token_type_title: title,
+      })
+    ).map(({ token_id }) => token_id);
+    assert.strictEqual(token_ids.length, 5);
+
+    // caller must own every token
+    try {
+      await bob.functionCall({
+        contractId,
+        methodName: "nft_approve_batch",
+        args: { token_ids, account_id: marketId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Predecessor must be token owner/.test(e.toString()));
+    }
+
+    for (const expected_approval_id of [1, 2]) {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_approve_batch",
+        args: { token_ids, account_id: marketId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      for (const token_id of token_ids) {
+        assert(
+          await contractAccount.viewFunction(contractId, "nft_is_approved", {
+            token_id,
+            approved_account_id: marketId,
+            approval_id: expected_approval_id,
+          })
+        );
+      }
+    }
+
+    // with `msg`, nft_on_approve is called on the market for every token, listing each of them
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_approve_batch",
+      args: {
+        token_ids,
+        account_id: marketId,
+        msg: JSON.stringify({
+          sale_conditions: { near: parseNearAmount("1") },
+          token_type: token_ids[0].split(TOKEN_DELIMETER)[0],
+          is_auction: false,
+        }),
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    for (const token_id of token_ids) {
+      const sale = await contractAccount.viewFunction(marketId, "get_sale", {
+        nft_contract_token: contractId + CONTRACT_TOKEN_DELIMETER + token_id,
+      });
+      assert.strictEqual(sale.owner_id, aliceId);
+      assert.strictEqual(sale.approval_id, 3);
+    }
+
+    // batch size is capped
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_approve_batch",
+        args: { token_ids: Array.from({ length: 11 }, (_, i) => token_ids[i % token_ids.length]), account_id: marketId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/token_ids must be less than or equal to 10/.test(e.toString()));
+    }
+  });
+
+
+  // migrate_token_types
+  it("should allow only the owner to migrate remaining V1 token types", async function () {
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "migrate_token_types",
+        args: { limit: 10 },
+        gas,
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    const token_types_before = await contractAccount.viewFunction(contractId, "nft_get_types");
+
+    // V1 token types can't be created on a freshly deployed contract, so there is nothing to migrate here;
+    // migrating must leave all current token types intact
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "migrate_token_types",
+      args: { limit: 10 },
+      gas,
+    });
+    const migrated = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+    assert.strictEqual(migrated, 0);
+
+    const token_types_after = await contractAccount.viewFunction(contractId, "nft_get_types");
+    assert.deepEqual(token_types_after, token_types_before);
+  });
+
+
+  // nft_delete_type event
+  it("should log an nft_delete_type event when the owner deletes a series", async function () {
+    const title = "series-to-be-deleted-event" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_delete_type",
+      args: {
+        token_type_title: title,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const logs = res.receipts_outcome.flatMap(({ outcome }) => outcome.logs);
+    const event = logs
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .find(({ event }) => event === "nft_delete_type");
+    assert(event);
+    assert.strictEqual(event.standard, "nft_series");
+    assert.strictEqual(event.data[0].token_type_title, title);
+    assert(event.data[0].token_type_id);
+
+    const token_types = await contractAccount.viewFunction(contractId, "nft_get_types");
+    assert(!token_types.some(({ metadata }) => metadata.title === title));
+  });
+
+
+  // nft_transfer_payout lazy mint
+  it("should pay out a lazy mint via nft_transfer_payout to the series owner, not the buyer", async function () {
+    const title = "lazy-mint-payout" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_set_approved_market",
+      args: {
+        token_type_title: title,
+        market_id: marketId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const balance = "1000000";
+    const lazyMintArgs = {
+      receiver_id: aliceId,
+      token_id: "",
+      approval_id: 0,
+      memo: "LAZY_MINT:" + JSON.stringify({ token_type_title: title, receiver_id: aliceId }),
+      balance,
+      max_len_payout: 10,
+    };
+
+    // only the type's approved market (or the contract owner) can lazy mint
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_transfer_payout",
+        args: lazyMintArgs,
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    const res = await market.functionCall({
+      contractId,
+      methodName: "nft_transfer_payout",
+      args: lazyMintArgs,
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { payout } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+
+    assert.strictEqual(payout[aliceId], undefined);
+    assert.strictEqual(payout[bobId], "100000");
+    assert.strictEqual(payout[contractId], "900000");
+
+    const tokens = await contractAccount.viewFunction(
+      contractId,
+      "nft_tokens_by_type",
+      { token_type_title: title }
+    );
+    assert.strictEqual(tokens.length, 1);
+    assert.strictEqual(tokens[0].owner_id, aliceId);
+  });
+
+
+  // nft_tokens_by_types
+  it("should return tokens across multiple types with pagination", async function () {
+    const now = Date.now();
+    const titles = [
+      "multi-type-a" + now,
+      "multi-type-b" + now,
+      "multi-type-c" + now,
+    ];
+    const supplies = [2, 3, 1];
+    const assets = [["some-asset-title.jpg", "10", ""]];
+
+    for (let i = 0; i < titles.length; i++) {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title: titles[i],
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies: 10,
+          },
+          assets,
+          royalty: {
+            [bobId]: BOB_ROYALTY,
+          },
+          cover_asset: assets[0][0],
+        },
+        parseNearAmount("1")
+      );
+      for (let j = 0; j < supplies[i]; j++) {
+        await contractAccount.functionCall({
+          contractId,
+          methodName: "nft_mint_type",
+          args: {
+            token_type_title: titles[i],
+            receiver_id: contractId,
+          },
+          gas,
+          attachedDeposit: parseNearAmount("0.1"),
+        });
+      }
+    }
+
+    const tokensFor = (token_type_titles, from_index, limit) =>
+      contractAccount.viewFunction(contractId, "nft_tokens_by_types", {
+        token_type_titles,
+        from_index,
+        limit,
+      });
+    const tokenIdsFor = async (title) =>
+      (
+        await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+          token_type_title: title,
+        })
+      ).map(({ token_id }) => token_id);
+
+    const idsA = await tokenIdsFor(titles[0]);
+    const idsB = await tokenIdsFor(titles[1]);
+    const idsC = await tokenIdsFor(titles[2]);
+
+    // two types
+    let tokens = await tokensFor(titles.slice(0, 2));
+    assert.deepEqual(tokens.map(({ token_id }) => token_id), [...idsA, ...idsB]);
+
+    // three types
+    const allIds = [...idsA, ...idsB, ...idsC];
+    tokens = await tokensFor(titles);
+    assert.deepEqual(tokens.map(({ token_id }) => token_id), allIds);
+
+    // page spanning the boundary between first and second type
+    tokens = await tokensFor(titles, "1", 2);
+    assert.deepEqual(tokens.map(({ token_id }) => token_id), allIds.slice(1, 3));
+
+    // page spanning second and third type, limit past end
+    tokens = await tokensFor(titles, "4", 10);
+    assert.deepEqual(tokens.map(({ token_id }) => token_id), allIds.slice(4));
+
+    // from_index at end returns empty
+    tokens = await tokensFor(titles, allIds.length.toString(), 10);
+    assert.strictEqual(tokens.length, 0);
+
+    // unknown title panics
+    try {
+      await tokensFor([titles[0], "no-such-type" + now]);
+      assert(false);
+    } catch (e) {
+      assert(/no type/.test(e.toString()));
+    }
+  });
+
+
+  // default royalty
+  it("should apply the default royalty to types created with an empty royalty", async function () {
+    const default_royalty = { [bobId]: 500 };
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "patch_default_royalty",
+        args: { default_royalty },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "patch_default_royalty",
+        args: { default_royalty: { [bobId]: 10001 } },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Royalty must not exceed 10000/.test(e.toString()));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_default_royalty",
+      args: { default_royalty },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    assert.deepEqual(
+      await contractAccount.viewFunction(contractId, "get_default_royalty"),
+      default_royalty
+    );
+
+    const title = "default-royalty" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {},
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const { royalty } = await contractAccount.viewFunction(contractId, "nft_get_type", {
+      token_type_title: title,
+    });
+    assert.deepEqual(royalty, default_royalty);
+
+    // reset so later types are unaffected
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_default_royalty",
+      args: { default_royalty: {} },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+  });
+
+
+  // storage_locked_for_token
+  it("should report storage locked by approvals on a token", async function () {
+    const title = "storage-locked" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+
+    let locked = await contractAccount.viewFunction(contractId, "storage_locked_for_token", { token_id });
+    assert.strictEqual(locked, "0");
+
+    for (const account_id of [aliceId, bobId]) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_approve",
+        args: { token_id, account_id },
+        gas,
+        attachedDeposit: parseNearAmount("0.01"),
+      });
+    }
+
+    // account id length + 4 bytes (borsh string length) + 8 bytes (approval id) per approved account
+    const STORAGE_BYTE_COST = new BN("10000000000000000000");
+    const expected = STORAGE_BYTE_COST.mul(
+      new BN(aliceId.length + 12 + bobId.length + 12)
+    );
+    locked = await contractAccount.viewFunction(contractId, "storage_locked_for_token", { token_id });
+    assert.strictEqual(locked, expected.toString());
+
+    // re-approving an already approved account locks no extra storage
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_approve",
+      args: { token_id, account_id: aliceId },
+      gas,
+      attachedDeposit: parseNearAmount("0.01"),
+    });
+    locked = await contractAccount.viewFunction(contractId, "storage_locked_for_token", { token_id });
+    assert.strictEqual(locked, expected.toString());
+  });
+
+
+  // nft_batch_mint_type with multiple assets
+  it("should batch mint a multi-asset type, selecting assets independently", async function () {
+    const title = "batch-multi-asset" + Date.now();
+    const assets = [
+      ["1.jpg", "10", ""],
+      ["2.jpg", "10", ""],
+      ["3.jpg", "10", ""],
+    ];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq",
+          copies: 30,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+
+    const receiver_ids = Array(9).fill(contractId);
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_batch_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_ids,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("1"),
+    });
+
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(tokens.length, receiver_ids.length);
+
+    const filenames = new Set(
+      tokens.map(({ metadata }) => metadata.media.split("/").pop())
+    );
+    // all 9 mints landing on the same asset has probability 3 * (1/3)^9 with independent selection
+    assert(filenames.size > 1);
+  });
+
+
+  // nft_token_media_url & nft_token_extra_url
+  it("should resolve token media & extra URLs as nft_token does", async function () {
+    const title = "token-urls" + Date.now();
+    const type_media = "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq";
+    const assets = [["1.jpg", "1", "1.json"], ["2.jpg", "1", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: type_media,
+          copies: 2,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    for (let i = 0; i < assets.length; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+
+    const { base_uri } = await contractAccount.viewFunction(contractId, "nft_metadata");
+    const withBaseUri = (path) =>
+      base_uri ? `${base_uri.replace(/\/$/, "")}/${path}` : path;
+
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+    });
+    for (const { token_id, metadata } of tokens) {
+      const media_url = await contractAccount.viewFunction(contractId, "nft_token_media_url", { token_id });
+      const extra_url = await contractAccount.viewFunction(contractId, "nft_token_extra_url", { token_id });
+      assert(metadata.media.startsWith(`${type_media}/`));
+      assert.strictEqual(media_url, withBaseUri(metadata.media));
+      if (metadata.media.endsWith("1.jpg")) {
+        assert.strictEqual(extra_url, withBaseUri(`${type_media}/1.json`));
+      } else {
+        assert.strictEqual(extra_url, null);
+      }
+    }
+
+    // unknown token
+    const media_url = await contractAccount.viewFunction(contractId, "nft_token_media_url", {
+      token_id: "999999:1",
+    });
+    assert.strictEqual(media_url, null);
+  });
+
+
+  // nft_cap_copies discards leftover asset supply
+  it("should NOT allow minting after copies are capped, regardless of leftover asset supply", async function () {
+    const title = "cap-assets" + Date.now();
+    const assets = [["1.jpg", "5", ""], ["2.jpg", "5", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_cap_copies",
+      args: { token_type_title: title },
+      gas,
+    });
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/type supply maxed/.test(e.toString()));
+    }
+  });
+
+
+  // nft_recategorize_token
+  it("should allow the owner to move a token to another type", async function () {
+    const now = Date.now();
+    const wrongTitle = "recategorize-wrong" + now;
+    const rightTitle = "recategorize-right" + now;
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    const royalties = { [wrongTitle]: 1000, [rightTitle]: 2000 };
+
+    for (const title of [wrongTitle, rightTitle]) {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies: 10,
+          },
+          assets,
+          royalty: {
+            [bobId]: royalties[title],
+          },
+          cover_asset: assets[0][0],
+        },
+        parseNearAmount("1")
+      );
+    }
+
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: wrongTitle,
+        receiver_id: aliceId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+    // a later mint doesn't prevent moving the earlier token
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: wrongTitle,
+        receiver_id: aliceId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_recategorize_token",
+        args: { token_id, new_type_title: rightTitle },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    const recategorized = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_recategorize_token",
+      args: { token_id, new_type_title: rightTitle },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const new_token = JSON.parse(
+      Buffer.from(recategorized.status.SuccessValue, "base64").toString()
+    );
+    const rightTypeId = new_token.token_id.split(":")[0];
+    assert.notStrictEqual(new_token.token_id, token_id);
+    assert.strictEqual(new_token.token_id, `${rightTypeId}:1`);
+    assert.strictEqual(new_token.owner_id, aliceId);
+
+    // old token_id no longer exists
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_token", { token_id }), null);
+
+    // enumeration follows new type
+    const wrongTokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: wrongTitle,
+    });
+    assert.deepEqual(wrongTokens.map(({ token_id }) => token_id), [`${token_id.split(":")[0]}:2`]);
+    const burned = await contractAccount.viewFunction(contractId, "nft_burned_supply_for_type", {
+      token_type_title: wrongTitle,
+    });
+    assert.strictEqual(burned, "1");
+    const rightTokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: rightTitle,
+    });
+    assert.deepEqual(rightTokens.map(({ token_id }) => token_id), [new_token.token_id]);
+    assert(rightTokens[0].metadata.title.startsWith(rightTitle));
+    const aliceTokens = await contractAccount.viewFunction(contractId, "nft_tokens_for_owner", {
+      account_id: aliceId,
+      limit: 500,
+    });
+    assert(aliceTokens.some(({ token_id: id }) => id === new_token.token_id));
+    assert(!aliceTokens.some(({ token_id: id }) => id === token_id));
+
+    // royalties follow new type
+    const { payout } = await contractAccount.viewFunction(contractId, "nft_payout", {
+      token_id: new_token.token_id,
+      balance: "1000000",
+      max_len_payout: 10,
+    });
+    assert.strictEqual(payout[bobId], "200000");
+    assert.strictEqual(payout[aliceId], "800000");
+
+    // the moved edition is never reissued
+    const next = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: wrongTitle,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const next_token = JSON.parse(
+      Buffer.from(next.status.SuccessValue, "base64").toString()
+    );
+    assert.strictEqual(next_token.token_id, `${token_id.split(":")[0]}:3`);
+
+    // tokens can't be moved into (or out of) a frozen type
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_freeze_type",
+      args: { token_type_title: rightTitle },
+      gas,
+    });
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_recategorize_token",
+        args: { token_id: next_token.token_id, new_type_title: rightTitle },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/type is frozen/.test(e.toString()));
+    }
+  });
+
+
+  // patch_contract_metadata
+  it("should allow the owner to patch individual contract metadata fields", async function () {
+    const before = await contractAccount.viewFunction(contractId, "nft_metadata");
+    const symbol = "PATCHED" + Date.now().toString().slice(-4);
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "patch_contract_metadata",
+        args: { symbol },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_contract_metadata",
+      args: { symbol },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const after = await contractAccount.viewFunction(contractId, "nft_metadata");
+    assert.deepEqual(after, { ...before, symbol });
+  });
+
+  it("should NOT allow the owner to patch contract reference without reference_hash", async function () {
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "patch_contract_metadata",
+        args: { reference: "https://example.com/contract.json" },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Reference and reference hash must be present/.test(e.toString()));
+    }
+  });
+
+
+  // nft_token_raw_metadata
+  it("should return stored token metadata alongside the assembled metadata", async function () {
+    const title = "raw-metadata" + Date.now();
+    const type_media = "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq";
+    const assets = [["1.jpg", "1", "1.json"], ["2.jpg", "1", "2.json"]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: type_media,
+          copies: 2,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+
+    const raw = await contractAccount.viewFunction(contractId, "nft_token_raw_metadata", { token_id });
+    const { metadata } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+
+    const asset = assets.find(([filename]) => filename === raw.media);
+    assert(asset);
+    assert.strictEqual(raw.extra, asset[2]);
+    assert.strictEqual(raw.title, null);
+    assert.strictEqual(raw.copies, null);
+
+    assert.strictEqual(metadata.media, `${type_media}/${raw.media}`);
+    assert.strictEqual(metadata.extra, `${type_media}/${raw.extra}`);
+    assert(metadata.title.startsWith(title));
+    assert.strictEqual(metadata.copies, 2);
+
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_token_raw_metadata", { token_id: "999999:1" }),
+      null
+    );
+  });
+
+
+  // title reservation only after validation
+  it("should NOT reserve a type title if nft_create_type fails validation", async function () {
+    const title = "unreserved-title" + Date.now();
+    const args = (assets) => ({
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies: 10,
+      },
+      assets,
+      royalty: {
+        [bobId]: BOB_ROYALTY,
+      },
+      cover_asset: assets[0][0],
+    });
+
+    try {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        args([["some-asset-title.jpg", "9", ""]]),
+        parseNearAmount("1")
+      );
+      assert(false);
+    } catch (e) {
+      assert(/Total supply must equal copies/.test(e.toString()));
+    }
+
+    try {
+      await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+      assert(false);
+    } catch (e) {
+      assert(/no type/.test(e.toString()));
+    }
+
+    // title is still available
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      args([["some-asset-title.jpg", "10", ""]]),
+      parseNearAmount("1")
+    );
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(token_type.metadata.title, title);
+
+    // renaming onto an existing title fails & leaves both titles intact
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_update_type",
+        args: {
+          token_type_title: title,
+          metadata: { title: token_type_title_non_gen },
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/token_metadata.title already exists/.test(e.toString()));
+    }
+    const renamed = await contractAccount.viewFunction(contractId, "nft_get_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(renamed.metadata.title, title);
+  });
+
+
+  // nft_admin_burn
+  it("should allow the owner to burn a token they don't own", async function () {
+    const title = "admin-burn" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const mintedIds = [];
+    for (let i = 0; i < 2; i++) {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: aliceId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      mintedIds.push(
+        JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id
+      );
+    }
+    const token_id = mintedIds[0];
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_admin_burn",
+        args: { token_id },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    const totalSupplyBefore = await contractAccount.viewFunction(contractId, "nft_total_supply");
+    const aliceSupplyBefore = await contractAccount.viewFunction(contractId, "nft_supply_for_owner", {
+      account_id: aliceId,
+    });
+
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_admin_burn",
+      args: { token_id },
+      gas,
+      attachedDeposit: "1",
+    });
+    const event = res.receipts_outcome
+      .flatMap(({ outcome }) => outcome.logs)
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .find(({ event }) => event === "nft_burn");
+    assert.deepEqual(event.data[0], {
+      owner_id: aliceId,
+      authorized_id: contractId,
+      token_ids: [token_id],
+    });
+
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_token", { token_id }), null);
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title }),
+      "1"
+    );
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_total_supply"),
+      (parseInt(totalSupplyBefore) - 1).toString()
+    );
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_supply_for_owner", { account_id: aliceId }),
+      (parseInt(aliceSupplyBefore) - 1).toString()
+    );
+
+    // burned editions are not reissued
+    const mintRes = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: aliceId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id: nextTokenId } = JSON.parse(
+      Buffer.from(mintRes.status.SuccessValue, "base64").toString()
+    );
+    assert(!mintedIds.includes(nextTokenId));
+    assert.strictEqual(nextTokenId.split(":")[1], "3");
+  });
+
+
+  // nft_get_types_by_owner
+  it("should return only the types owned by a given account", async function () {
+    const allTypes = await contractAccount.viewFunction(contractId, "nft_get_types");
+    const ownedByContract = allTypes.filter(({ owner_id }) => owner_id === contractId);
+    assert(ownedByContract.length > 2);
+
+    const types = await contractAccount.viewFunction(contractId, "nft_get_types_by_owner", {
+      owner_id: contractId,
+    });
+    assert.deepEqual(types, ownedByContract);
+
+    // pagination applies to the filtered list
+    const page = await contractAccount.viewFunction(contractId, "nft_get_types_by_owner", {
+      owner_id: contractId,
+      from_index: "1",
+      limit: 2,
+    });
+    assert.deepEqual(page, ownedByContract.slice(1, 3));
+
+    // types can only be created by the contract owner, so other accounts own none
+    const aliceTypes = await contractAccount.viewFunction(contractId, "nft_get_types_by_owner", {
+      owner_id: aliceId,
+    });
+    assert.deepEqual(aliceTypes, []);
+  });
+
+
+  // AssetDetail validation
+  it("should NOT allow the owner to create a type with malformed assets", async function () {
+    const cases = [
+      { assets: [["1.jpg", "5", ""], ["2.jpg", "5"]], error: /Asset at index 1 must have 3 elements/ },
+      { assets: [["1.jpg", "5", "", "extra"], ["2.jpg", "5", ""]], error: /Asset at index 0 has invalid sha256 'extra'/ },
+      { assets: [["1.jpg", "5", ""], ["2.jpg", "five", ""]], error: /Asset at index 1 has invalid supply 'five'/ },
+      { assets: [["1.jpg", "10", ""], ["2.jpg", "0", ""]], error: /Asset at index 1 must have a supply greater than 0/ },
+    ];
+
+    for (const { assets, error } of cases) {
+      try {
+        await testUtils.createType(
+          contractAccount,
+          contractId,
+          {
+            metadata: {
+              title: "malformed-assets" + Date.now(),
+              media: "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq",
+              copies: 10,
+            },
+            assets,
+            royalty: {
+              [bobId]: BOB_ROYALTY,
+            },
+            cover_asset: assets[0][0],
+          },
+          parseNearAmount("1")
+        );
+        assert(false);
+      } catch (e) {
+        assert(error.test(e.toString()), e.toString());
+      }
+    }
+  });
+
+
+  // nft_tokens_by_type_paged
+  it("should return a next cursor until the last page of a type's tokens", async function () {
+    const title = "paged" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const supply = 5;
+    for (let i = 0; i < supply; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+    const allTokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+    });
+
+    const pagedTokens = [];
+    const cursors = [];
+    let from_index = "0";
+    while (from_index !== null) {
+      const [tokens, next] = await contractAccount.viewFunction(contractId, "nft_tokens_by_type_paged", {
+        token_type_title: title,
+        from_index,
+        limit: 2,
+      });
+      pagedTokens.push(...tokens);
+      cursors.push(next);
+      from_index = next;
+    }
+    assert.deepEqual(cursors, ["2", "4", null]);
+    assert.deepEqual(pagedTokens, allTokens);
+
+    // a page ending exactly at the last token has no next cursor
+    const [tokens, next] = await contractAccount.viewFunction(contractId, "nft_tokens_by_type_paged", {
+      token_type_title: title,
+      from_index: "3",
+      limit: 2,
+    });
+    assert.strictEqual(tokens.length, 2);
+    assert.strictEqual(next, null);
+  });
+
+
+  // MAX_ROYALTY_RECIPIENTS
+  it("should NOT allow the owner to create or update a type with more than 10 royalty recipients", async function () {
+    const royalty = {};
+    for (let i = 0; i < 11; i++) {
+      royalty[`recipient${i}.${contractId}`] = 100;
+    }
+    const title = "too-many-royalties" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    const args = {
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies: 10,
+      },
+      assets,
+      royalty,
+      cover_asset: assets[0][0],
+    };
+
+    try {
+      await testUtils.createType(contractAccount, contractId, args, parseNearAmount("1"));
+      assert(false);
+    } catch (e) {
+      assert(/Royalty cannot have more than 10 recipients/.test(e.toString()));
+    }
+
+    // 10 recipients is allowed
+    delete royalty[`recipient10.${contractId}`];
+    await testUtils.createType(contractAccount, contractId, args, parseNearAmount("1"));
+
+    royalty[`recipient10.${contractId}`] = 100;
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_update_type",
+        args: { token_type_title: title, royalty },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Royalty cannot have more than 10 recipients/.test(e.toString()));
+    }
+  });
+
+
+  // nft_preview_token
+  it("should preview token metadata as nft_token assembles it", async function () {
+    const title = "preview" + Date.now();
+    const assets = [["preview.jpg", "10", "preview.json"]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+
+    const preview = await contractAccount.viewFunction(contractId, "nft_preview_token", {
+      token_type_title: title,
+      edition: 1,
+    });
+
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+    const { metadata } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    // preview isn't stored, so has no `updated_at`
+    assert.strictEqual(preview.updated_at, null);
+    assert.deepEqual({ ...preview, updated_at: metadata.updated_at }, metadata);
+
+    // edition is clamped to [1, copies]
+    const [, delimiter_title, delimiter_edition] = await contractAccount.viewFunction(
+      contractId,
+      "nft_get_type_format"
+    );
+    const last = await contractAccount.viewFunction(contractId, "nft_preview_token", {
+      token_type_title: title,
+      edition: 100,
+    });
+    assert.strictEqual(last.title, `${title}${delimiter_title}10${delimiter_edition}10`);
+    const first = await contractAccount.viewFunction(contractId, "nft_preview_token", {
+      token_type_title: title,
+      edition: 0,
+    });
+    assert.strictEqual(first.title, metadata.title);
+  });
+
+
+  // patch_type_format
+  it("should allow the owner to configure title & edition delimiters", async function () {
+    const [DEFAULT_TOKEN_DELIMETER, DEFAULT_TITLE_DELIMETER, DEFAULT_EDITION_DELIMETER] =
+      await contractAccount.viewFunction(contractId, "nft_get_type_format");
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "patch_type_format",
+        args: { title_delimeter: " #" },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_type_format",
+      args: { title_delimeter: " #", edition_delimeter: " of " },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    assert.deepEqual(
+      await contractAccount.viewFunction(contractId, "nft_get_type_format"),
+      [DEFAULT_TOKEN_DELIMETER, " #", " of "]
+    );
+
+    const title = "custom-format" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+    const { metadata } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    assert.strictEqual(metadata.title, `${title} #1 of 10`);
+
+    // reset so later tests see default format
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_type_format",
+      args: { title_delimeter: DEFAULT_TITLE_DELIMETER, edition_delimeter: DEFAULT_EDITION_DELIMETER },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+  });
+
+
+  // patch_media_and_assets_for_token_type supply calculation
+  it("should patch media & assets of a capped type without supply underflow", async function () {
+    const title = "patch-capped" + Date.now();
+    const assets = [["old.jpg", "5", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    for (let i = 0; i < 2; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_cap_copies",
+      args: { token_type_title: title },
+      gas,
+    });
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "patch_media_and_assets_for_token_type",
+        args: {
+          token_type_title: title,
+          media: "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq",
+          assets: [["new.jpg", "0"]],
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Asset must have 3 elements/.test(e.toString()));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_media_and_assets_for_token_type",
+      args: {
+        token_type_title: title,
+        media: "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq",
+        assets: [["new.jpg", "0", ""]],
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(tokens.length, 2);
+    tokens.forEach(({ metadata }) =>
+      assert.strictEqual(
+        metadata.media,
+        "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq/new.jpg"
+      )
+    );
+
+    // capped supply remains exhausted
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/type supply maxed/.test(e.toString()));
+    }
+  });
+
+
+  // burned supply accounting
+  it("should track burned supply separately from live supply", async function () {
+    const title = "burned-supply" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const tokenIds = [];
+    for (let i = 0; i < 3; i++) {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: aliceId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      tokenIds.push(
+        JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id
+      );
+    }
+
+    const burnedBefore = await contractAccount.viewFunction(contractId, "nft_burned_supply");
+    const totalBefore = await contractAccount.viewFunction(contractId, "nft_total_supply");
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_admin_burn",
+      args: { token_id: tokenIds[1] },
+      gas,
+      attachedDeposit: "1",
+    });
+
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title }),
+      "2"
+    );
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_burned_supply_for_type", { token_type_title: title }),
+      "1"
+    );
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_burned_supply"),
+      (parseInt(burnedBefore) + 1).toString()
+    );
+    // minted-ever count is preserved
+    const totalAfter = await contractAccount.viewFunction(contractId, "nft_total_supply");
+    const burnedAfter = await contractAccount.viewFunction(contractId, "nft_burned_supply");
+    assert.strictEqual(
+      parseInt(totalAfter) + parseInt(burnedAfter),
+      parseInt(totalBefore) + parseInt(burnedBefore)
+    );
+
+    // capping copies counts burned NFTs as minted
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_cap_copies",
+      args: { token_type_title: title },
+      gas,
+    });
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(token_type.metadata.copies, 3);
+  });
+
+
+  // nft_transfer_payout plain memo
+  it("should treat an unprefixed memo in nft_transfer_payout as a plain transfer", async function () {
+    const title = "plain-memo-payout" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const mintRes = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(mintRes.status.SuccessValue, "base64").toString()
+    );
+
+    // a memo that isn't LAZY_MINT-prefixed (even if it's TypeMintArgs JSON) transfers the given token
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_transfer_payout",
+      args: {
+        receiver_id: aliceId,
+        token_id,
+        approval_id: 0,
+        memo: JSON.stringify({ token_type_title: title, receiver_id: aliceId }),
+        balance: "1000000",
+        max_len_payout: 10,
+      },
+      gas,
+      attachedDeposit: "1",
+    });
+    const { payout } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+    assert.strictEqual(payout[contractId], "900000");
+
+    const { owner_id } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    assert.strictEqual(owner_id, aliceId);
+    const supply = await contractAccount.viewFunction(contractId, "nft_supply_for_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(supply, "1");
+  });
+
+
+  // nft_token_approvals
+  it("should enumerate approvals for a token", async function () {
+    const title = "token-approvals" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+
+    let approvals = await contractAccount.viewFunction(contractId, "nft_token_approvals", { token_id });
+    assert.deepStrictEqual(approvals, {});
+
+    for (const account_id of [aliceId, bobId]) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_approve",
+        args: { token_id, account_id },
+        gas,
+        attachedDeposit: parseNearAmount("0.01"),
+      });
+    }
+
+    approvals = await contractAccount.viewFunction(contractId, "nft_token_approvals", { token_id });
+    assert.deepStrictEqual(approvals, { [aliceId]: 1, [bobId]: 2 });
+  });
+
+
+  // patch_max_batch_size
+  it("should enforce the configured max batch size in nft_batch_mint_type", async function () {
+    const title = "max-batch-size" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+
+    const defaultMaxBatchSize = await contractAccount.viewFunction(contractId, "get_max_batch_size");
+    assert.strictEqual(defaultMaxBatchSize, "1000");
+
+    // cap must be greater than 0
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "patch_max_batch_size",
+        args: { max_batch_size: "0" },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes("max_batch_size must be greater than 0"));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_max_batch_size",
+      args: { max_batch_size: "2" },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "get_max_batch_size"), "2");
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_batch_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_ids: Array(3).fill(contractId),
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes("receiver_ids must be less than or equal to 2"));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_batch_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_ids: Array(2).fill(contractId),
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const supply = await contractAccount.viewFunction(contractId, "nft_supply_for_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(supply, "2");
+
+    // restore default for subsequent tests
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_max_batch_size",
+      args: { max_batch_size: defaultMaxBatchSize },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+  });
+
+
+  // nft_token_with_royalty
+  it("should return a token along with its type's royalty", async function () {
+    const title = "token-with-royalty" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+
+    const [token, royalty] = await contractAccount.viewFunction(contractId, "nft_token_with_royalty", { token_id });
+    const expectedToken = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    assert.deepStrictEqual(token, expectedToken);
+
+    const tokenType = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.deepStrictEqual(royalty, tokenType.royalty);
+    assert.strictEqual(royalty[bobId], BOB_ROYALTY);
+
+    const missing = await contractAccount.viewFunction(contractId, "nft_token_with_royalty", {
+      token_id: token_id + "999",
+    });
+    assert.strictEqual(missing, null);
+  });
+
+
+  // nft_mint_type with token-level metadata
+  it("should let a minted token override its type's title & description", async function () {
+    const title = "token-metadata-override" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          description: "type description",
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+
+    const mint = async (metadata) => {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+          metadata,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      return JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id;
+    };
+    const customTokenId = await mint({ title: "One of a kind", description: "unique description" });
+    const siblingTokenId = await mint(null);
+
+    const customToken = await contractAccount.viewFunction(contractId, "nft_token", { token_id: customTokenId });
+    assert.strictEqual(customToken.metadata.title, "One of a kind — 1/10");
+    assert.strictEqual(customToken.metadata.description, "unique description");
+
+    const siblingToken = await contractAccount.viewFunction(contractId, "nft_token", { token_id: siblingTokenId });
+    assert.strictEqual(siblingToken.metadata.title, `${title} — 2/10`);
+    assert.strictEqual(siblingToken.metadata.description, "type description");
+  });
+
+
+  // contract_metadata_update event
+  it("should log contract_metadata_update only when base_uri actually changes", async function () {
+    const { base_uri: originalBaseUri } = await contractAccount.viewFunction(contractId, "nft_metadata");
+    const newBaseUri = "https://gateway.example.com/ipfs/" + Date.now();
+
+    const patchBaseUri = async (base_uri) => {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "patch_base_uri",
+        args: { base_uri },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      return res.receipts_outcome
+        .flatMap(({ outcome }) => outcome.logs)
+        .filter((log) => log.startsWith("EVENT_JSON:"))
+        .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+        .find(({ event }) => event === "contract_metadata_update");
+    };
+
+    const event = await patchBaseUri(newBaseUri);
+    assert(event);
+    assert.strictEqual(event.standard, "nft_series");
+    assert.deepStrictEqual(event.data[0].fields, ["base_uri"]);
+
+    // no-op (same value) logs nothing
+    assert.strictEqual(await patchBaseUri(newBaseUri), undefined);
+
+    if (originalBaseUri) {
+      await patchBaseUri(originalBaseUri);
+    }
+  });
+
+
+  // nft_is_minter
+  it("should report the contract owner (and only the owner) as a minter", async function () {
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_is_minter", { account_id: contractId }),
+      true
+    );
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_is_minter", { account_id: aliceId }),
+      false
+    );
+  });
+
+
+  // refund_or_reward_storage
+  it("should not require a deposit for a patch that frees storage", async function () {
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_default_royalty",
+      args: { default_royalty: { [aliceId]: 100, [bobId]: 100 } },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    // clearing the default royalty frees storage, so no deposit is needed (freed storage is refunded to the owner)
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_default_royalty",
+      args: { default_royalty: {} },
+      gas,
+      attachedDeposit: "0",
+    });
+    assert(res.status.SuccessValue !== undefined);
+
+    const defaultRoyalty = await contractAccount.viewFunction(contractId, "get_default_royalty");
+    assert.deepStrictEqual(defaultRoyalty, {});
+  });
+
+
+  // nft_tokens & nft_tokens_by_type reverse
+  it("should enumerate tokens newest first when reverse is set", async function () {
+    const title = "reverse-order" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_batch_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_ids: Array(3).fill(contractId),
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const ascending = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+    });
+    const descending = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+      reverse: true,
+    });
+    assert.strictEqual(ascending.length, 3);
+    assert.deepStrictEqual(
+      descending.map(({ token_id }) => token_id),
+      ascending.map(({ token_id }) => token_id).reverse()
+    );
+    assert(ascending[0].token_id.endsWith(":1"));
+    assert(descending[0].token_id.endsWith(":3"));
+
+    const tokenIds = await contractAccount.viewFunction(contractId, "nft_token_ids");
+    const [first] = await contractAccount.viewFunction(contractId, "nft_tokens", { limit: 1 });
+    const [last] = await contractAccount.viewFunction(contractId, "nft_tokens", { limit: 1, reverse: true });
+    assert.strictEqual(first.token_id, tokenIds[0]);
+    assert.strictEqual(last.token_id, tokenIds[tokenIds.length - 1]);
+  });
+
+
+  // nft_mint_type after migrate_token_types
+  it("should mint into a type after migrate_token_types has run", async function () {
+    const title = "mint-after-migration" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "migrate_token_types",
+      args: { limit: 10 },
+      gas,
+    });
+
+    // NB: V1 token types can't be created on a freshly deployed contract, so minting into an un-migrated type (which migrates it
+    // on the fly) can't be exercised here; types already in the current map must be unaffected
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+    assert(token_id.endsWith(":1"));
+    const supply = await contractAccount.viewFunction(contractId, "nft_supply_for_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(supply, "1");
+  });
+
+
+  // nft_token with malformed token_id
+  it("should return null from nft_token for token_ids not of the form {type}:{edition}", async function () {
+    for (const token_id of ["legacy-token", "not-a-type:1", ":"]) {
+      const token = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+      assert.strictEqual(token, null);
+    }
+
+    // bulk enumeration is unaffected
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens", { limit: 10 });
+    assert(tokens.every((token) => token && token.metadata));
+  });
+
+
+  // nft_set_cover_asset
+  it("should allow the owner to update only a type's cover asset", async function () {
+    const title = "set-cover-asset" + Date.now();
+    const media = "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq";
+    const assets = [
+      ["1.jpg", "10", ""],
+      ["2.jpg", "10", ""],
+    ];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media,
+          copies: 20,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+    const tokenBefore = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_set_cover_asset",
+        args: { token_type_title: title, cover_asset: "2.jpg" },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_set_cover_asset",
+      args: { token_type_title: title, cover_asset: "2.jpg" },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const tokenType = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(tokenType.metadata.media, `${media}/2.jpg`);
+
+    const tokenAfter = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    assert.deepStrictEqual(tokenAfter.metadata, tokenBefore.metadata);
+  });
+
+
+  // patch_min_royalty
+  it("should reject types whose royalty omits or underpays a mandated min_royalty recipient", async function () {
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_min_royalty",
+      args: { min_royalty: { [aliceId]: 250 } },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    assert.deepStrictEqual(
+      await contractAccount.viewFunction(contractId, "get_min_royalty"),
+      { [aliceId]: 250 }
+    );
+
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    const createType = (title, royalty) =>
+      testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies: 10,
+          },
+          assets,
+          royalty,
+          cover_asset: assets[0][0],
+        },
+        parseNearAmount("1")
+      );
+
+    try {
+      // omits mandated recipient
+      await createType("min-royalty-omitted" + Date.now(), { [bobId]: BOB_ROYALTY });
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes(`Royalty must pay ${aliceId} at least 250`));
+    }
+
+    try {
+      // underpays mandated recipient
+      await createType("min-royalty-underpaid" + Date.now(), { [bobId]: BOB_ROYALTY, [aliceId]: 100 });
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes(`Royalty must pay ${aliceId} at least 250`));
+    }
+
+    const title = "min-royalty-paid" + Date.now();
+    await createType(title, { [bobId]: BOB_ROYALTY, [aliceId]: 250 });
+
+    try {
+      // royalty updates are enforced too
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_update_royalty",
+        args: { token_type_title: title, royalty: { [bobId]: BOB_ROYALTY } },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes(`Royalty must pay ${aliceId} at least 250`));
+    }
+
+    // remove minimum for subsequent tests
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_min_royalty",
+      args: { min_royalty: {} },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+  });
+
+
+  // contract_storage_usage & contract_storage_cost
+  it("should report contract storage usage growing after creating a type", async function () {
+    const usageBefore = await contractAccount.viewFunction(contractId, "contract_storage_usage");
+    const costBefore = await contractAccount.viewFunction(contractId, "contract_storage_cost");
+
+    const title = "storage-usage" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+
+    const usageAfter = await contractAccount.viewFunction(contractId, "contract_storage_usage");
+    const costAfter = await contractAccount.viewFunction(contractId, "contract_storage_cost");
+    assert(new BN(usageAfter).gt(new BN(usageBefore)));
+    assert(new BN(costAfter).gt(new BN(costBefore)));
+
+    // cost is usage * storage byte cost (10^19 yoctoNEAR per byte)
+    const STORAGE_BYTE_COST = new BN("10000000000000000000");
+    assert.strictEqual(costAfter, STORAGE_BYTE_COST.mul(new BN(usageAfter)).toString());
+  });
+
+
+  // nft_revoke_all_batch
+  it("should revoke all approvals on several tokens in one call", async function () {
+    const title = "revoke-all-batch" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_batch_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_ids: Array(3).fill(contractId),
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const token_ids = (
+      await contractAccount.viewFunction(contractId, "nft_tokens_by_type", { token_type_title: title })
+    ).map(({ token_id }) => token_id);
+    assert.strictEqual(token_ids.length, 3);
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_approve_batch",
+      args: { token_ids, account_id: aliceId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    for (const token_id of token_ids) {
+      assert(
+        await contractAccount.viewFunction(contractId, "nft_is_approved", { token_id, approved_account_id: aliceId })
+      );
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_revoke_all_batch",
+      args: { token_ids },
+      gas,
+      attachedDeposit: "1",
+    });
+    for (const token_id of token_ids) {
+      assert.strictEqual(
+        await contractAccount.viewFunction(contractId, "nft_is_approved", { token_id, approved_account_id: aliceId }),
+        false
+      );
+    }
+  });
+
+
+  // nft_create_type with mint_all_to
+  it("should mint all copies to mint_all_to when creating a type", async function () {
+    const title = "mint-all-on-create" + Date.now();
+    const assets = [
+      ["1.jpg", "2", ""],
+      ["2.jpg", "1", ""],
+    ];
+    const res = await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq",
+          copies: 3,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+        mint_all_to: aliceId,
+      },
+      parseNearAmount("1")
+    );
+
+    const mintEvents = res.receipts_outcome
+      .flatMap(({ outcome }) => outcome.logs)
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .filter(({ event }) => event === "nft_mint");
+    assert.strictEqual(mintEvents.length, 1);
+    assert.strictEqual(mintEvents[0].data[0].owner_id, aliceId);
+    assert.strictEqual(mintEvents[0].data[0].token_ids.length, 3);
+
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", { token_type_title: title });
+    assert.strictEqual(tokens.length, 3);
+    assert(tokens.every(({ owner_id }) => owner_id === aliceId));
+    // all asset supply is minted
+    const media = tokens.map(({ metadata }) => metadata.media.split("/").pop()).sort();
+    assert.deepStrictEqual(media, ["1.jpg", "1.jpg", "2.jpg"]);
+  });
+
+
+  // patch_max_copies_per_type
+  it("should enforce the configured max copies per type in nft_create_type", async function () {
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "get_max_copies_per_type"), null);
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_max_copies_per_type",
+      args: { max_copies_per_type: "10" },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "get_max_copies_per_type"), "10");
+
+    const createType = (title, copies) =>
+      testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies,
+          },
+          assets: [["some-asset-title.jpg", copies.toString(), ""]],
+          royalty: {
+            [bobId]: BOB_ROYALTY,
+          },
+          cover_asset: "some-asset-title.jpg",
+        },
+        parseNearAmount("1")
+      );
+
+    try {
+      await createType("max-copies-exceeded" + Date.now(), 11);
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes("token_metadata.copies must not exceed 10"));
+    }
+    await createType("max-copies-within" + Date.now(), 10);
+
+    // `None` disables the check
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_max_copies_per_type",
+      args: { max_copies_per_type: null },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const title = "max-copies-disabled" + Date.now();
+    await createType(title, 11);
+    const tokenType = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(tokenType.metadata.copies, 11);
+  });
+
+
+  // nft_lock & nft_unlock
+  it("should only let the locker transfer a locked token", async function () {
+    const title = "locked-token" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(res.status.SuccessValue, "base64").toString()
+    );
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_approve",
+      args: { token_id, account_id: aliceId },
+      gas,
+      attachedDeposit: parseNearAmount("0.01"),
+    });
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_lock",
+      args: { token_id, locker_id: aliceId },
+      gas,
+      attachedDeposit: parseNearAmount("0.01"),
+    });
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_locked_by", { token_id }), aliceId);
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_transfer",
+        args: { token_id, receiver_id: bobId },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes(`Token is locked by ${aliceId}`));
+    }
+
+    // bob has no contract, so nft_on_transfer fails and the NFT is returned - still locked
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_transfer_call",
+      args: { token_id, receiver_id: bobId, msg: "" },
+      gas,
+      attachedDeposit: "1",
+    });
+    assert.strictEqual((await contractAccount.viewFunction(contractId, "nft_token", { token_id })).owner_id, contractId);
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_locked_by", { token_id }), aliceId);
+
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_transfer",
+      args: { token_id, receiver_id: bobId },
+      gas,
+      attachedDeposit: "1",
+    });
+    const { owner_id } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    assert.strictEqual(owner_id, bobId);
+    // lock doesn't carry over to the new owner
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_locked_by", { token_id }), null);
+  });
+
+
+  // nft_transfer_payout max_len_payout default
+  it("should default max_len_payout in nft_transfer_payout and panic clearly when exceeded", async function () {
+    const title = "max-len-payout-default" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [aliceId]: 500,
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const mint = async () => {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      return JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id;
+    };
+    const token_id = await mint();
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_transfer_payout",
+        args: { receiver_id: aliceId, token_id, approval_id: 0, balance: "1000000", max_len_payout: 1 },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(e.toString().includes("Royalty has 2 receivers, which exceeds max_len_payout of 1"));
+    }
+
+    // types can't have more than MAX_ROYALTY_RECIPIENTS (the default), so omitting max_len_payout always succeeds
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_transfer_payout",
+      args: { receiver_id: aliceId, token_id, approval_id: 0, balance: "1000000" },
+      gas,
+      attachedDeposit: "1",
+    });
+    const { payout } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    assert.strictEqual(Object.keys(payout).length, 3);
+  });
+
+
+  // nft_public_mint with primary_sale_split
+  it("should split public mint proceeds per royalty when primary_sale_split is set", async function () {
+    const title = "primary-sale-split-" + Date.now();
+    const price = parseNearAmount("1");
+    const assets = [["public.jpg", "10", ""]];
+
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: 1000,
+          [marketId]: 2000,
+        },
+        cover_asset: assets[0][0],
+        price,
+        primary_sale_split: true,
+      },
+      parseNearAmount("0.1")
+    );
+    const tokenType = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(tokenType.primary_sale_split, true);
+
+    const bobBalanceBefore = (await getAccountBalance(bobId)).total;
+    const marketBalanceBefore = (await getAccountBalance(marketId)).total;
+    const ownerBalanceBefore = (await getAccountBalance(contractId)).total;
+
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_public_mint",
+      args: { token_type_title: title },
+      gas,
+      attachedDeposit: parseNearAmount("1.1"),
+    });
+
+    const bobReceived = new BN((await getAccountBalance(bobId)).total).sub(new BN(bobBalanceBefore));
+    const marketReceived = new BN((await getAccountBalance(marketId)).total).sub(new BN(marketBalanceBefore));
+    const ownerReceived = new BN((await getAccountBalance(contractId)).total).sub(new BN(ownerBalanceBefore));
+    assert.strictEqual(bobReceived.toString(), parseNearAmount("0.1")); // 10%
+    assert.strictEqual(marketReceived.toString(), parseNearAmount("0.2")); // 20%
+    // type owner receives the remaining 70% (plus storage, retained by the contract)
+    assert(ownerReceived.gte(new BN(parseNearAmount("0.7"))));
+    assert(ownerReceived.lt(new BN(parseNearAmount("0.8"))));
+  });
+
+
+  // nft_tokens_for_owner_by_types
+  it("should enumerate an owner's tokens restricted to the given types", async function () {
+    const now = Date.now();
+    const titles = ["owner-by-types-a" + now, "owner-by-types-b" + now, "owner-by-types-c" + now];
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    for (const title of titles) {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies: 10,
+          },
+          assets,
+          royalty: {
+            [bobId]: BOB_ROYALTY,
+          },
+          cover_asset: assets[0][0],
+        },
+        parseNearAmount("1")
+      );
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: bobId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_for_owner_by_types", {
+      account_id: bobId,
+      token_type_titles: [titles[0], titles[2]],
+    });
+    assert.strictEqual(tokens.length, 2);
+    assert(tokens.every(({ owner_id }) => owner_id === bobId));
+    const tokenTitles = tokens.map(({ metadata }) => metadata.title).sort();
+    assert(tokenTitles[0].startsWith(titles[0]));
+    assert(tokenTitles[1].startsWith(titles[2]));
+
+    const page = await contractAccount.viewFunction(contractId, "nft_tokens_for_owner_by_types", {
+      account_id: bobId,
+      token_type_titles: [titles[0], titles[2]],
+      from_index: "1",
+      limit: 1,
+    });
+    assert.strictEqual(page.length, 1);
+    assert.strictEqual(page[0].token_id, tokens[1].token_id);
+  });
+
+
+  it("should compute a type's payout matching nft_payout for a token held by the type owner", async function () {
+    const title = "type-payout" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+          [aliceId]: 500,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const token = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(
+      Buffer.from(token.status.SuccessValue, "base64").toString()
+    );
+
+    const typePayout = await contractAccount.viewFunction(contractId, "nft_type_payout", {
+      token_type_title: title,
+      balance: "10000",
+      max_len_payout: 10,
+    });
+    const tokenPayout = await contractAccount.viewFunction(contractId, "nft_payout", {
+      token_id,
+      balance: "10000",
+      max_len_payout: 10,
+    });
+    assert.deepStrictEqual(typePayout, tokenPayout);
+    assert.strictEqual(typePayout.payout[aliceId], "500");
+  });
+
+
+  it("should never reissue the token_id of a burned NFT", async function () {
+    const title = "next-edition" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const mint = async () => {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: aliceId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      return JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id;
+    };
+    const tokenIds = [await mint(), await mint(), await mint()];
+    const typeId = tokenIds[0].split(":")[0];
+    assert.deepEqual(tokenIds, [1, 2, 3].map((edition) => `${typeId}:${edition}`));
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_admin_burn",
+      args: { token_id: tokenIds[1] },
+      gas,
+      attachedDeposit: "1",
+    });
+
+    const newTokenId = await mint();
+    assert.strictEqual(newTokenId, `${typeId}:4`);
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "nft_token", { token_id: tokenIds[1] }),
+      null
+    );
+    const token = await contractAccount.viewFunction(contractId, "nft_token", { token_id: newTokenId });
+    // display edition number follows the token_id
+    assert(token.metadata.title.endsWith("4/10"));
+  });
+
+
+  it("should get a type by its numeric id, matching the title-based lookup", async function () {
+    const title = "type-by-id" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: aliceId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    const token_type_id = parseInt(token_id.split(":")[0]);
+
+    const byId = await contractAccount.viewFunction(contractId, "nft_get_type_by_id", { token_type_id });
+    const byTitle = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.deepStrictEqual(byId, byTitle);
+
+    try {
+      await contractAccount.viewFunction(contractId, "nft_get_type_by_id", { token_type_id: 4294967295 });
+      assert(false);
+    } catch (e) {
+      assert(/no type/.test(e.toString()));
+    }
+  });
+
+
+  // storage management (NEP-145)
+  it("should draw mint storage from a registered account's storage balance and allow withdrawing the rest", async function () {
+    const bounds = await contractAccount.viewFunction(contractId, "storage_balance_bounds");
+    assert.strictEqual(bounds.max, null);
+
+    await alice.functionCall({
+      contractId,
+      methodName: "storage_deposit",
+      args: {},
+      gas,
+      attachedDeposit: parseNearAmount("0.5"),
+    });
+    const before = await contractAccount.viewFunction(contractId, "storage_balance_of", { account_id: aliceId });
+    assert.strictEqual(before.total, parseNearAmount("0.5"));
+    assert.strictEqual(
+      before.available,
+      new BN(before.total).sub(new BN(bounds.min)).toString()
+    );
+
+    const title = "storage-deposit" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    // no deposit attached; storage is paid from alice's storage balance
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: aliceId,
+      },
+      gas,
+    });
+    const after = await contractAccount.viewFunction(contractId, "storage_balance_of", { account_id: aliceId });
+    assert(new BN(after.available).lt(new BN(before.available)));
+
+    await alice.functionCall({
+      contractId,
+      methodName: "storage_withdraw",
+      args: {},
+      gas,
+      attachedDeposit: "1",
+    });
+    const withdrawn = await contractAccount.viewFunction(contractId, "storage_balance_of", { account_id: aliceId });
+    assert.strictEqual(withdrawn.available, "0");
+    assert.strictEqual(withdrawn.total, bounds.min);
+
+    assert.strictEqual(
+      await contractAccount.viewFunction(contractId, "storage_balance_of", { account_id: bobId }),
+      null
+    );
+  });
+
+
+  it("should aggregate royalty recipients across types weighted by copies", async function () {
+    const now = Date.now();
+    const treasuryId = "treasury-" + now + "." + contractId;
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    for (const [title, copies, royalty] of [
+      ["royalty-recipients-a" + now, 10, 500],
+      ["royalty-recipients-b" + now, 20, 1000],
+    ]) {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies,
+          },
+          assets,
+          royalty: {
+            [bobId]: BOB_ROYALTY,
+            [treasuryId]: royalty,
+          },
+          cover_asset: assets[0][0],
+        },
+        parseNearAmount("1")
+      );
+    }
+
+    const recipients = await contractAccount.viewFunction(contractId, "nft_all_royalty_recipients");
+    assert.strictEqual(recipients[treasuryId], (500 * 10 + 1000 * 20).toString());
+    assert(parseInt(recipients[bobId]) >= BOB_ROYALTY * 30);
+  });
+
+
+  it("should return copies for every type, including any migrated from V1", async function () {
+    // V1 types can't be created by this contract version, so this covers legacy types only on contracts upgraded from V1
+    const token_types = await contractAccount.viewFunction(contractId, "nft_get_types");
+    assert(token_types.length > 0);
+    for (const { metadata, original_copies } of token_types) {
+      assert.strictEqual(typeof metadata.copies, "number");
+      assert.strictEqual(typeof original_copies, "number");
+    }
+  });
+
+
+  it("should return minted NFTs with metadata as returned by nft_token", async function () {
+    const title = "mint-return" + Date.now();
+    const assets = [["1.jpg", "5", "1.json"]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: aliceId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const minted = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    const token = await contractAccount.viewFunction(contractId, "nft_token", { token_id: minted.token_id });
+
+    assert.strictEqual(minted.metadata.media, token.metadata.media);
+    assert.strictEqual(minted.metadata.extra, token.metadata.extra);
+    assert.strictEqual(minted.metadata.title, token.metadata.title);
+    assert(minted.metadata.media.endsWith("/1.jpg"));
+  });
+
+
+  it("should approve NFTs of a type with an approved market for that market on mint", async function () {
+    const title = "approved-market" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_set_approved_market",
+      args: {
+        token_type_title: title,
+        market_id: marketId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(token_type.approved_market_id, marketId);
+
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: aliceId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const token = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    assert.deepStrictEqual(token.approved_account_ids, { [marketId]: 1 });
+    assert(
+      await contractAccount.viewFunction(contractId, "nft_is_approved", {
+        token_id: token.token_id,
+        approved_account_id: marketId,
+      })
+    );
+
+    // only contract owner can set the approved market
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_set_approved_market",
+        args: {
+          token_type_title: title,
+          market_id: null,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+  });
+
+
+  it("should cap enumeration limit", async function () {
+    for (const [methodName, args] of [
+      ["nft_tokens", {}],
+      ["nft_tokens_for_owner", { account_id: aliceId }],
+    ]) {
+      const tokens = await contractAccount.viewFunction(contractId, methodName, { ...args, limit: 500 });
+      assert(tokens.length <= 500);
+      try {
+        await contractAccount.viewFunction(contractId, methodName, { ...args, limit: 501 });
+        assert(false);
+      } catch (e) {
+        assert(/Cannot provide limit greater than 500/.test(e.toString()));
+      }
+    }
+  });
+
+
+  it("should record block timestamp of type creation", async function () {
+    const title = "created-at" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const { created_at } = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    // nanoseconds; allow for clock skew between test runner and chain
+    const createdAtMs = Number(BigInt(created_at) / 1000000n);
+    assert(Math.abs(Date.now() - createdAtMs) < 10 * 60 * 1000);
+  });
+
+
+  it("should clear all royalties of a type when updated with an empty royalty", async function () {
+    const title = "clear-royalty" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: aliceId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_update_type",
+      args: {
+        token_type_title: title,
+        royalty: {},
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.deepStrictEqual(token_type.royalty, {});
+    const { payout } = await contractAccount.viewFunction(contractId, "nft_payout", {
+      token_id,
+      balance: "1000000",
+      max_len_payout: 10,
+    });
+    assert.deepStrictEqual(payout, { [aliceId]: "1000000" });
+  });
+
+
+  it("should mint up to copies and no further, counting burned NFTs", async function () {
+    const title = "supply-boundary" + Date.now();
+    const assets = [["some-asset-title.jpg", "2", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 2,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const mint = () =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    await mint();
+    const res = await mint();
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    assert(token_id.endsWith(":2"));
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_admin_burn",
+      args: { token_id },
+      gas,
+      attachedDeposit: "1",
+    });
+
+    // burned edition still counts towards copies
+    try {
+      await mint();
+      assert(false);
+    } catch (e) {
+      assert(/type supply maxed/.test(e.toString()));
+    }
+  });
+
+
+  it("should list token_ids of a type, matching nft_tokens_by_type", async function () {
+    const title = "token-ids-by-type" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_batch_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_ids: [aliceId, bobId, contractId],
+      },
+      gas,
+      attachedDeposit: parseNearAmount("1"),
+    });
+
+    const token_ids = await contractAccount.viewFunction(contractId, "nft_token_ids_by_type", {
+      token_type_title: title,
+    });
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+    });
+    assert.strictEqual(token_ids.length, 3);
+    assert.deepStrictEqual(token_ids, tokens.map(({ token_id }) => token_id));
+
+    const page = await contractAccount.viewFunction(contractId, "nft_token_ids_by_type", {
+      token_type_title: title,
+      from_index: "1",
+      limit: 1,
+    });
+    assert.deepStrictEqual(page, [token_ids[1]]);
+  });
+
+
+  it("should reject type titles containing reserved delimiters", async function () {
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    const typeArgs = (title) => ({
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies: 10,
+      },
+      assets,
+      royalty: {
+        [bobId]: BOB_ROYALTY,
+      },
+      cover_asset: assets[0][0],
+    });
+    const now = Date.now();
+    for (const title of ["Series:1" + now, "Series/1" + now]) {
+      try {
+        await testUtils.createType(contractAccount, contractId, typeArgs(title), parseNearAmount("1"));
+        assert(false);
+      } catch (e) {
+        assert(/must not contain reserved delimiter/.test(e.toString()));
+      }
+    }
+
+    const title = "Series-1-" + now;
+    await testUtils.createType(contractAccount, contractId, typeArgs(title), parseNearAmount("1"));
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(token_type.metadata.title, title);
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_update_type",
+        args: {
+          token_type_title: title,
+          metadata: { title: "Series:2" + now },
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/must not contain reserved delimiter/.test(e.toString()));
+    }
+  });
+
+
+  it("should log supply change on cap and block updates once frozen", async function () {
+    const title = "cap-freeze-events" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const findEvent = (res, name) =>
+      res.receipts_outcome
+        .flatMap(({ outcome }) => outcome.logs)
+        .filter((log) => log.startsWith("EVENT_JSON:"))
+        .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+        .find(({ event }) => event === name);
+
+    const capRes = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_cap_copies",
+      args: { token_type_title: title },
+      gas,
+    });
+    const capEvent = findEvent(capRes, "nft_type_supply_change");
+    assert(capEvent);
+    assert.strictEqual(capEvent.standard, "nft_series");
+    assert.strictEqual(capEvent.data[0].token_type_title, title);
+    assert.strictEqual(capEvent.data[0].old_copies, 10);
+    assert.strictEqual(capEvent.data[0].new_copies, 1);
+
+    const freezeRes = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_freeze_type",
+      args: { token_type_title: title },
+      gas,
+    });
+    const freezeEvent = findEvent(freezeRes, "nft_type_frozen");
+    assert(freezeEvent);
+    assert.strictEqual(freezeEvent.data[0].token_type_id, capEvent.data[0].token_type_id);
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(token_type.frozen, true);
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_update_royalty",
+        args: {
+          token_type_title: title,
+          royalty: {},
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/type is frozen/.test(e.toString()));
+    }
+  });
+
+
+  it("should transfer an NFT by type title & edition, as nft_transfer by token_id", async function () {
+    const title = "transfer-by-edition" + Date.now();
+    const assets = [["some-asset-title.jpg", "10", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const token_ids = [];
+    for (let i = 0; i < 2; i++) {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      token_ids.push(JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id);
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_transfer",
+      args: {
+        receiver_id: aliceId,
+        token_id: token_ids[0],
+      },
+      gas,
+      attachedDeposit: "1",
+    });
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_transfer_by_edition",
+      args: {
+        receiver_id: aliceId,
+        token_type_title: title,
+        edition: 2,
+      },
+      gas,
+      attachedDeposit: "1",
+    });
+    for (const token_id of token_ids) {
+      const { owner_id } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+      assert.strictEqual(owner_id, aliceId);
+    }
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_transfer_by_edition",
+        args: {
+          receiver_id: bobId,
+          token_type_title: title,
+          edition: 3,
+        },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Edition 3 was never minted/.test(e.toString()));
+    }
+  });
+
+
+  it("should append assets to a type, raising copies", async function () {
+    const title = "add-assets" + Date.now();
+    const assets = [
+      ["1.jpg", "1", ""],
+      ["2.jpg", "1", ""],
+    ];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 2,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const mint = () =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    await mint();
+    await mint();
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_add_assets",
+        args: {
+          token_type_title: title,
+          new_assets: [["3.jpg", "2"]],
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/must have 3 elements/.test(e.toString()));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_add_assets",
+      args: {
+        token_type_title: title,
+        new_assets: [["3.jpg", "2", "3.json"]],
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(token_type.metadata.copies, 4);
+
+    const res = await mint();
+    const { metadata } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    assert(metadata.media.endsWith("/3.jpg"));
+    await mint();
+
+    // capped types can't be extended
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_cap_copies",
+      args: { token_type_title: title },
+      gas,
+    });
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_add_assets",
+        args: {
+          token_type_title: title,
+          new_assets: [["4.jpg", "1", ""]],
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/type is capped/.test(e.toString()));
+    }
+  });
+
+
+  it("should return an owner's tokens of a type with their total count", async function () {
+    const title = "owner-by-type" + Date.now();
+    const assets = [["1.jpg", "5", "1.json"]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets,
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_batch_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_ids: [aliceId, bobId, aliceId, bobId, bobId],
+      },
+      gas,
+      attachedDeposit: parseNearAmount("1"),
+    });
+
+    const [tokens, total] = await contractAccount.viewFunction(contractId, "nft_tokens_for_owner_by_type", {
+      account_id: aliceId,
+      token_type_title: title,
+    });
+    assert.strictEqual(total, "2");
+    assert.strictEqual(tokens.length, 2);
+    for (const token of tokens) {
+      assert.strictEqual(token.owner_id, aliceId);
+      assert.deepStrictEqual(
+        token,
+        await contractAccount.viewFunction(contractId, "nft_token", { token_id: token.token_id })
+      );
+      assert(token.metadata.media.endsWith("/1.jpg"));
+    }
+
+    const [page, pageTotal] = await contractAccount.viewFunction(contractId, "nft_tokens_for_owner_by_type", {
+      account_id: aliceId,
+      token_type_title: title,
+      from_index: "1",
+      limit: 1,
+    });
+    assert.strictEqual(pageTotal, "2");
+    assert.deepStrictEqual(page, [tokens[1]]);
+  });
+
+
+  it("should reject a type with zero copies and accept a type with one", async function () {
+    const now = Date.now();
+    const typeArgs = (title, copies, assets) => ({
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies,
+      },
+      assets,
+      royalty: {
+        [bobId]: BOB_ROYALTY,
+      },
+      cover_asset: "1.jpg",
+    });
+    try {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        typeArgs("zero-copies" + now, 0, [["1.jpg", "0", ""]]),
+        parseNearAmount("1")
+      );
+      assert(false);
+    } catch (e) {
+      assert(/copies must be greater than 0/.test(e.toString()));
+    }
+
+    const title = "one-copy" + now;
+    await testUtils.createType(contractAccount, contractId, typeArgs(title, 1, [["1.jpg", "1", ""]]), parseNearAmount("1"));
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(token_type.metadata.copies, 1);
+  });
+
+
+  it("should let the owner withdraw free balance but not storage-locked balance", async function () {
+    // NEAR sent to the contract by mistake
+    await alice.sendMoney(contractId, parseNearAmount("1"));
+
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "withdraw_near",
+        args: {
+          amount: parseNearAmount("1000000000"),
+          to: bobId,
+        },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Cannot withdraw more than free balance/.test(e.toString()));
+    }
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "withdraw_near",
+        args: {
+          amount: parseNearAmount("0.5"),
+          to: aliceId,
+        },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    const bobBalanceBefore = (await getAccountBalance(bobId)).total;
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "withdraw_near",
+      args: {
+        amount: parseNearAmount("0.5"),
+        to: bobId,
+      },
+      gas,
+      attachedDeposit: "1",
+    });
+    const bobBalanceAfter = (await getAccountBalance(bobId)).total;
+    assert.strictEqual(
+      new BN(bobBalanceAfter).sub(new BN(bobBalanceBefore)).toString(),
+      parseNearAmount("0.5")
+    );
+  });
+
+
+  it("should skip reserved editions on mint and let only the reservee claim them", async function () {
+    const title = `Reserved Type ${Date.now()}`;
+    const copies = 6;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies,
+        },
+        assets: [["1.jpg", copies.toString(), ""]],
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_reserve_edition",
+      args: {
+        token_type_title: title,
+        edition: 5,
+        account_id: bobId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    // sequential mints skip edition 5
+    const editions = [];
+    for (let i = 0; i < copies - 1; i++) {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: contractId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      const token = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+      editions.push(token.token_id.split(":")[1]);
+    }
+    assert.deepStrictEqual(editions, ["1", "2", "3", "4", "6"]);
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_claim_reserved",
+        args: {
+          token_type_title: title,
+          edition: 5,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/is not reserved for/.test(e.toString()));
+    }
+
+    const res = await bob.functionCall({
+      contractId,
+      methodName: "nft_claim_reserved",
+      args: {
+        token_type_title: title,
+        edition: 5,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const token = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    assert.strictEqual(token.token_id.split(":")[1], "5");
+    assert.strictEqual(token.owner_id, bobId);
+
+    // reservation is used up
+    try {
+      await bob.functionCall({
+        contractId,
+        methodName: "nft_claim_reserved",
+        args: {
+          token_type_title: title,
+          edition: 5,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/is not reserved for/.test(e.toString()));
+    }
+  });
+
+
+  it("should panic with stable error messages for common failure modes", async function () {
+    const expectPanic = async (promise, message) => {
+      try {
+        await promise;
+        assert(false);
+      } catch (e) {
+        assert(e.toString().includes(message), `expected "${message}", got ${e}`);
+      }
+    };
+    const title = `Errors Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 1,
+        },
+        assets: [["1.jpg", "1", ""]],
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const mint = (account, token_type_title) =>
+      account.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title,
+          receiver_id: bobId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+
+    await expectPanic(mint(alice, title), "Unauthorized");
+    await expectPanic(mint(contractAccount, `${title} (missing)`), "no type");
+    const res = await mint(contractAccount, title);
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    await expectPanic(mint(contractAccount, title), "type supply maxed");
+
+    await expectPanic(
+      alice.functionCall({
+        contractId,
+        methodName: "nft_approve",
+        args: { token_id, account_id: marketId },
+        gas,
+        attachedDeposit: parseNearAmount("0.01"),
+      }),
+      "Predecessor must be token owner."
+    );
+    await expectPanic(
+      bob.functionCall({
+        contractId,
+        methodName: "nft_transfer",
+        args: { receiver_id: aliceId, token_id: `${token_id}0` },
+        gas,
+        attachedDeposit: "1",
+      }),
+      "Token not found"
+    );
+    await expectPanic(
+      bob.functionCall({
+        contractId,
+        methodName: "nft_transfer",
+        args: { receiver_id: bobId, token_id },
+        gas,
+        attachedDeposit: "1",
+      }),
+      "Current and next owner must differ"
+    );
+  });
+
+
+  it("should omit NFTs of unrevealed types from nft_tokens & nft_tokens_by_type when include_unrevealed is false", async function () {
+    const now = Date.now();
+    const revealedTitle = `Revealed Type ${now}`;
+    const hiddenTitle = `Hidden Type ${now}`;
+    const tokenIds = {};
+    for (const title of [revealedTitle, hiddenTitle]) {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies: 1,
+          },
+          assets: [["1.jpg", "1", ""]],
+          royalty: {
+            [bobId]: BOB_ROYALTY,
+          },
+          cover_asset: "1.jpg",
+        },
+        parseNearAmount("1")
+      );
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: bobId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      tokenIds[title] = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id;
+    }
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_set_type_revealed",
+      args: {
+        token_type_title: hiddenTitle,
+        revealed: false,
+      },
+      gas,
+    });
+    const hiddenType = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: hiddenTitle });
+    assert.strictEqual(hiddenType.revealed, false);
+
+    const byType = (token_type_title, include_unrevealed) =>
+      contractAccount.viewFunction(contractId, "nft_tokens_by_type", { token_type_title, include_unrevealed });
+    assert.strictEqual((await byType(hiddenTitle)).length, 1);
+    assert.strictEqual((await byType(hiddenTitle, false)).length, 0);
+    assert.strictEqual((await byType(revealedTitle, false)).length, 1);
+
+    const allTokenIds = async (include_unrevealed) => {
+      const limit = 500;
+      let ids = [];
+      for (let from_index = 0; ; from_index += limit) {
+        const page = await contractAccount.viewFunction(contractId, "nft_tokens", {
+          from_index: from_index.toString(),
+          limit,
+          include_unrevealed,
+        });
+        ids = ids.concat(page.map(({ token_id }) => token_id));
+        if (page.length < limit) return ids;
+      }
+    };
+    const unfiltered = await allTokenIds();
+    assert(unfiltered.includes(tokenIds[hiddenTitle]));
+    assert(unfiltered.includes(tokenIds[revealedTitle]));
+    const filtered = await allTokenIds(false);
+    assert(!filtered.includes(tokenIds[hiddenTitle]));
+    assert(filtered.includes(tokenIds[revealedTitle]));
+  });
+
+
+  it("should reject approving the token owner", async function () {
+    const title = `Self Approval Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 1,
+        },
+        assets: [["1.jpg", "1", ""]],
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: bobId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+
+    try {
+      await bob.functionCall({
+        contractId,
+        methodName: "nft_approve",
+        args: { token_id, account_id: bobId },
+        gas,
+        attachedDeposit: parseNearAmount("0.01"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Token owner and approved account must differ/.test(e.toString()));
+    }
+
+    await bob.functionCall({
+      contractId,
+      methodName: "nft_approve",
+      args: { token_id, account_id: aliceId },
+      gas,
+      attachedDeposit: parseNearAmount("0.01"),
+    });
+    assert(await contractAccount.viewFunction(contractId, "nft_is_approved", { token_id, approved_account_id: aliceId }));
+  });
+
+
+  it("should report circulating supply excluding the treasury's NFTs", async function () {
+    const title = `Circulating Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 3,
+        },
+        assets: [["1.jpg", "3", ""]],
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const circulating = async () =>
+      new BN(await contractAccount.viewFunction(contractId, "nft_circulating_supply", { exclude: contractId }));
+    const before = await circulating();
+
+    for (const receiver_id of [contractId, contractId, bobId]) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+
+    const after = await circulating();
+    assert.strictEqual(after.sub(before).toString(), "1");
+    const total = new BN(await contractAccount.viewFunction(contractId, "nft_total_supply", {}));
+    const treasury = new BN(await contractAccount.viewFunction(contractId, "nft_supply_for_owner", { account_id: contractId }));
+    assert.strictEqual(after.toString(), total.sub(treasury).toString());
+  });
+
+
+  it("should store optional per-asset sha256 hashes and expose them via nft_get_type_assets", async function () {
+    const title = `Hashed Assets Type ${Date.now()}`;
+    const hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+    const typeArgs = (title, assets) => ({
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies: 2,
+      },
+      assets,
+      royalty: {
+        [bobId]: BOB_ROYALTY,
+      },
+      cover_asset: "1.jpg",
+    });
+
+    try {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        typeArgs(`${title} (invalid)`, [["1.jpg", "1", "", "abcd"], ["2.jpg", "1", ""]]),
+        parseNearAmount("1")
+      );
+      assert(false);
+    } catch (e) {
+      assert(/invalid sha256/.test(e.toString()));
+    }
+
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      typeArgs(title, [["1.jpg", "1", "", hash], ["2.jpg", "1", ""]]),
+      parseNearAmount("1")
+    );
+    const assets = await contractAccount.viewFunction(contractId, "nft_get_type_assets", { token_type_title: title });
+    assert.deepStrictEqual(assets, [["1.jpg", "1", "", hash], ["2.jpg", "1", ""]]);
+
+    // 4-element assets still mint as usual
+    for (let i = 0; i < 2; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: bobId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", { token_type_title: title });
+    assert.deepStrictEqual(tokens.map(({ metadata }) => metadata.media.split("/").pop()).sort(), ["1.jpg", "2.jpg"]);
+  });
+
+
+  it("should mint only once for repeated nft_mint_type calls with the same mint_id", async function () {
+    const title = `Idempotent Mint Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets: [["1.jpg", "5", ""]],
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const mint = async (mint_id) => {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: bobId,
+          mint_id,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      return JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    };
+
+    const first = await mint("order-1");
+    const retried = await mint("order-1");
+    assert.strictEqual(retried.token_id, first.token_id);
+    const other = await mint("order-2");
+    assert.notStrictEqual(other.token_id, first.token_id);
+
+    const supply = await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title });
+    assert.strictEqual(supply, "2");
+  });
+
+
+  it("should return the next edition to be minted, incrementing after each mint", async function () {
+    const title = `Next Edition Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 3,
+        },
+        assets: [["1.jpg", "3", ""]],
+        royalty: {
+          [bobId]: BOB_ROYALTY,
+        },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const nextEdition = () => contractAccount.viewFunction(contractId, "nft_next_edition", { token_type_title: title });
+
+    for (let edition = 1; edition <= 2; edition++) {
+      assert.strictEqual(await nextEdition(), edition.toString());
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_id: bobId,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+      assert.strictEqual(token_id.split(":")[1], edition.toString());
+    }
+    assert.strictEqual(await nextEdition(), "3");
+
+    try {
+      await contractAccount.viewFunction(contractId, "nft_next_edition", { token_type_title: `${title} (missing)` });
+      assert(false);
+    } catch (e) {
+      assert(/no type/.test(e.toString()));
+    }
+  });
+
+
+  it("should block public mints while a type is disabled, but still allow owner mints", async function () {
+    const title = `Disabled Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets: [["1.jpg", "10", ""]],
+        royalty: {},
+        cover_asset: "1.jpg",
+        price: parseNearAmount("0.1"),
+      },
+      parseNearAmount("0.1")
+    );
+    const setEnabled = (enabled) =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "nft_set_type_enabled",
+        args: { token_type_title: title, enabled },
+        gas,
+      });
+    const publicMint = () =>
+      alice.functionCall({
+        contractId,
+        methodName: "nft_public_mint",
+        args: { token_type_title: title },
+        gas,
+        attachedDeposit: parseNearAmount("1"),
+      });
+
+    await setEnabled(false);
+    assert.strictEqual(
+      (await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title })).enabled,
+      false
+    );
+    try {
+      await publicMint();
+      assert(false);
+    } catch (e) {
+      assert(/type is disabled/.test(e.toString()));
+    }
+    // market sales (lazy mints) are public mints too
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_set_approved_market",
+      args: { token_type_title: title, market_id: marketId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    try {
+      await market.functionCall({
+        contractId,
+        methodName: "nft_transfer_payout",
+        args: {
+          receiver_id: aliceId,
+          token_id: "",
+          approval_id: 0,
+          memo: "LAZY_MINT:" + JSON.stringify({ token_type_title: title, receiver_id: aliceId }),
+          balance: parseNearAmount("0.1"),
+          max_len_payout: 10,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/type is disabled/.test(e.toString()));
+    }
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: bobId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    await setEnabled(true);
+    await publicMint();
+    const supply = await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title });
+    assert.strictEqual(supply, "2");
+  });
+
+
+  it("should pay rounding dust to the owner so nft_payout sums exactly to balance", async function () {
+    const title = `Payout Dust Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 1,
+        },
+        assets: [["1.jpg", "1", ""]],
+        // 3.33% & 12.5% of an odd balance don't divide evenly
+        royalty: {
+          [bobId]: 333,
+          [aliceId]: 1250,
+        },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: {
+        token_type_title: title,
+        receiver_id: contractId,
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+
+    const balance = "1000000000000000000000007";
+    const { payout } = await contractAccount.viewFunction(contractId, "nft_payout", {
+      token_id,
+      balance,
+      max_len_payout: 9,
+    });
+    const total = Object.values(payout).reduce((sum, amount) => sum.add(new BN(amount)), new BN(0));
+    assert.strictEqual(total.toString(), balance);
+    // royalty recipients get their truncated share; owner gets the rest
+    assert.strictEqual(payout[bobId], new BN(balance).muln(333).divn(10000).toString());
+    assert.strictEqual(payout[aliceId], new BN(balance).muln(1250).divn(10000).toString());
+  });
+
+
+  it("should return spec, version & commit sha together from nft_version_info", async function () {
+    const [spec, version, commit] = await contractAccount.viewFunction(contractId, "nft_version_info", {});
+    const metadata = await contractAccount.viewFunction(contractId, "nft_metadata", {});
+    const sourceMetadata = await contractAccount.viewFunction(contractId, "contract_source_metadata", {});
+    assert.strictEqual(spec, metadata.spec);
+    assert.strictEqual(version, sourceMetadata ? sourceMetadata.version : null);
+    assert.strictEqual(commit, sourceMetadata ? sourceMetadata.commit_sha : null);
+  });
+
+
+  it("should panic clearly when nft_transfer_call has too little gas for the receiver call & resolve", async function () {
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_transfer_call",
+        args: {
+          receiver_id: marketId,
+          token_id,
+          msg: "",
+        },
+        gas: "20000000000000",
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Must attach more than 30000000000000 gas to nft_transfer_call/.test(e.toString()));
+    }
+  });
+
+
+  it("should log identically shaped nft_mint & nft_transfer events regardless of call site", async function () {
+    const title = `Events Type ${Date.now()}`;
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets: [["1.jpg", "5", ""]],
+        royalty: {},
+        cover_asset: "1.jpg",
+        price: parseNearAmount("0.1"),
+      },
+      parseNearAmount("0.1")
+    );
+    const eventsOf = (res, event) =>
+      res.receipts_outcome
+        .flatMap(({ outcome }) => outcome.logs)
+        .filter((log) => log.startsWith("EVENT_JSON:"))
+        .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+        .filter((log) => log.event === event);
+    const shape = ({ standard, version, event, data }) => ({
+      standard,
+      version,
+      event,
+      keys: Object.keys(data[0]).sort(),
+    });
+
+    const ownerMint = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: { token_type_title: title, receiver_id: bobId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const publicMint = await alice.functionCall({
+      contractId,
+      methodName: "nft_public_mint",
+      args: { token_type_title: title },
+      gas,
+      attachedDeposit: parseNearAmount("1"),
+    });
+    const [ownerMintEvent] = eventsOf(ownerMint, "nft_mint");
+    const [publicMintEvent] = eventsOf(publicMint, "nft_mint");
+    assert.deepStrictEqual(shape(ownerMintEvent), shape(publicMintEvent));
+    assert.deepStrictEqual(shape(ownerMintEvent).keys, ["owner_id", "token_ids"]);
+
+    // transfer by owner (with memo) vs. transfer by approved market via nft_transfer_payout
+    const { token_id } = JSON.parse(Buffer.from(ownerMint.status.SuccessValue, "base64").toString());
+    const ownerTransfer = await bob.functionCall({
+      contractId,
+      methodName: "nft_transfer",
+      args: { receiver_id: aliceId, token_id, memo: "gift" },
+      gas,
+      attachedDeposit: "1",
+    });
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_approve",
+      args: { token_id, account_id: marketId },
+      gas,
+      attachedDeposit: parseNearAmount("0.01"),
+    });
+    const { approved_account_ids } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    const marketTransfer = await market.functionCall({
+      contractId,
+      methodName: "nft_transfer_payout",
+      args: {
+        receiver_id: bobId,
+        token_id,
+        approval_id: approved_account_ids[marketId],
+        memo: "sale",
+        balance: parseNearAmount("1"),
+        max_len_payout: 9,
+      },
+      gas,
+      attachedDeposit: "1",
+    });
+    const [ownerTransferEvent] = eventsOf(ownerTransfer, "nft_transfer");
+    const marketTransferEvents = eventsOf(marketTransfer, "nft_transfer");
+    assert.strictEqual(marketTransferEvents.length, 1);
+    const [marketTransferEvent] = marketTransferEvents;
+    assert.deepStrictEqual(shape(ownerTransferEvent).keys, ["memo", "new_owner_id", "old_owner_id", "token_ids"]);
+    assert.deepStrictEqual(shape(marketTransferEvent).keys, ["authorized_id", "memo", "new_owner_id", "old_owner_id", "token_ids"]);
+    assert.strictEqual(marketTransferEvent.data[0].authorized_id, marketId);
+    assert.strictEqual(ownerTransferEvent.standard, marketTransferEvent.standard);
+    assert.strictEqual(ownerTransferEvent.version, marketTransferEvent.version);
+  });
+
+
+  it("should use the same royalty denominator for validation and payouts", async function () {
+    const denominator = await contractAccount.viewFunction(contractId, "nft_royalty_denominator", {});
+    assert.strictEqual(denominator, 10000);
+    const typeArgs = (title, royalty) => ({
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies: 1,
+      },
+      assets: [["1.jpg", "1", ""]],
+      royalty,
+      cover_asset: "1.jpg",
+    });
+    const title = `Denominator Type ${Date.now()}`;
+
+    try {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        typeArgs(`${title} (invalid)`, { [bobId]: denominator + 1 }),
+        parseNearAmount("1")
+      );
+      assert(false);
+    } catch (e) {
+      assert(new RegExp(`Royalty must not exceed ${denominator}`).test(e.toString()));
+    }
+
+    // a quarter of the denominator is a quarter of the balance
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      typeArgs(title, { [bobId]: denominator / 4 }),
+      parseNearAmount("1")
+    );
+    const { payout } = await contractAccount.viewFunction(contractId, "nft_type_payout", {
+      token_type_title: title,
+      balance: "1000",
+      max_len_payout: 9,
+    });
+    assert.strictEqual(payout[bobId], "250");
+    assert.strictEqual(payout[contractId], "750");
+  });
+
+
+  it("should return an empty page (not panic) for a known type with no NFTs", async function () {
+    const title = "unminted-enum" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 5,
+        },
+        assets: [["1.jpg", "5", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+
+    const tokens = await contractAccount.viewFunction(contractId, "nft_tokens_by_type", {
+      token_type_title: title,
+      from_index: "5",
+    });
+    assert.deepStrictEqual(tokens, []);
+    const tokenIds = await contractAccount.viewFunction(contractId, "nft_token_ids_by_type", {
+      token_type_title: title,
+      from_index: "5",
+    });
+    assert.deepStrictEqual(tokenIds, []);
+    const supply = await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title });
+    assert.strictEqual(supply, "0");
+
+    // unknown titles still panic
+    try {
+      await contractAccount.viewFunction(contractId, "nft_tokens_by_type", { token_type_title: title + "-missing" });
+      assert(false);
+    } catch (e) {
+      assert(/no type/.test(e.toString()));
+    }
+  });
+
+
+  it("should split unminted assets of a type into a new type", async function () {
+    const title = "split-source" + Date.now();
+    const newTitle = title + "-split";
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 10,
+        },
+        assets: [
+          ["1.jpg", "2", "1.json"],
+          ["2.jpg", "2", "2.json"],
+          ["3.jpg", "2", "3.json"],
+          ["4.jpg", "2", "4.json"],
+          ["5.jpg", "2", "5.json"],
+        ],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+
+    const split = (asset_filenames) =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "nft_split_type",
+        args: {
+          source_title: title,
+          new_metadata: { title: newTitle },
+          asset_filenames,
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+
+    try {
+      await split(["6.jpg"]);
+      assert(false);
+    } catch (e) {
+      assert(/Asset 6.jpg not found/.test(e.toString()));
+    }
+
+    await split(["3.jpg", "4.jpg"]);
+
+    const source = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(source.metadata.copies, 6);
+    const sourceAssets = await contractAccount.viewFunction(contractId, "nft_get_type_assets", { token_type_title: title });
+    assert.deepStrictEqual(sourceAssets.map((asset) => asset[0]), ["1.jpg", "2.jpg", "5.jpg"]);
+
+    const newType = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: newTitle });
+    assert.strictEqual(newType.metadata.copies, 4);
+    assert.strictEqual(newType.metadata.media, source.metadata.media);
+    assert.strictEqual(newType.royalty[bobId], BOB_ROYALTY);
+    const newAssets = await contractAccount.viewFunction(contractId, "nft_get_type_assets", { token_type_title: newTitle });
+    assert.deepStrictEqual(newAssets.map((asset) => asset[0]), ["3.jpg", "4.jpg"]);
+
+    // minted assets stay with the source
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: { token_type_title: title, receiver_id: contractId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { metadata } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    const mintedAsset = metadata.media.split("/").pop();
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_split_type",
+        args: {
+          source_title: title,
+          new_metadata: { title: newTitle + "-2" },
+          asset_filenames: [mintedAsset],
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/has already been minted/.test(e.toString()));
+    }
+
+    // the source must keep more than one asset, so its NFT titles stay in filename format
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_split_type",
+        args: {
+          source_title: title,
+          new_metadata: { title: newTitle + "-3" },
+          asset_filenames: ["1.jpg", "2.jpg", "5.jpg"].filter((filename) => filename !== mintedAsset),
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Cannot leave the source with a single asset/.test(e.toString()));
+    }
+  });
+
+
+  it("should only transfer to registered receivers if require_receiver_registration is set", async function () {
+    const title = "registered-receiver" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 2,
+        },
+        assets: [["1.jpg", "2", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const mint = async () => {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id: contractId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      return JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id;
+    };
+    const transfer = (receiver_id, token_id) =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "nft_transfer",
+        args: { receiver_id, token_id },
+        gas,
+        attachedDeposit: "1",
+      });
+    const setRequired = (require_receiver_registration) =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "patch_require_receiver_registration",
+        args: { require_receiver_registration },
+        gas,
+      });
+
+    // alice registered, market not
+    await alice.functionCall({
+      contractId,
+      methodName: "storage_deposit",
+      args: { registration_only: true },
+      gas,
+      attachedDeposit: parseNearAmount("0.5"),
+    });
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "storage_balance_of", { account_id: marketId }), null);
+
+    const firstTokenId = await mint();
+    const secondTokenId = await mint();
+
+    await setRequired(true);
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "get_require_receiver_registration", {}), true);
+    try {
+      await transfer(marketId, firstTokenId);
+      assert(false);
+    } catch (e) {
+      assert(/Receiver is not registered for storage/.test(e.toString()));
+    }
+    await transfer(aliceId, firstTokenId);
+    let token = await contractAccount.viewFunction(contractId, "nft_token", { token_id: firstTokenId });
+    assert.strictEqual(token.owner_id, aliceId);
+
+    // off (default): unregistered receivers are fine
+    await setRequired(false);
+    await transfer(marketId, secondTokenId);
+    token = await contractAccount.viewFunction(contractId, "nft_token", { token_id: secondTokenId });
+    assert.strictEqual(token.owner_id, marketId);
+  });
+
+
+  it("should return supply remaining per asset", async function () {
+    const title = "asset-supply" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 4,
+        },
+        assets: [
+          ["1.jpg", "1", "1.json"],
+          ["2.jpg", "3", "2.json"],
+        ],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    let supply = await contractAccount.viewFunction(contractId, "nft_type_asset_supply", { token_type_title: title });
+    assert.deepStrictEqual(supply, [["1.jpg", 1], ["2.jpg", 3]]);
+
+    for (let i = 0; i < 2; i++) {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id: contractId },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+    }
+
+    supply = await contractAccount.viewFunction(contractId, "nft_type_asset_supply", { token_type_title: title });
+    const remaining = supply.reduce((sum, [, supplyRemaining]) => sum + supplyRemaining, 0);
+    assert.strictEqual(remaining, 2);
+    // sold-out assets are excluded
+    assert(supply.every(([, supplyRemaining]) => supplyRemaining > 0));
+    assert(supply.length === 1 || supply.length === 2);
+  });
+
+
+  it("should set & return contract metadata extra", async function () {
+    const extra = JSON.stringify({
+      description: "A long collection description",
+      links: { twitter: "https://twitter.com/satori" },
+    });
+    const patchExtra = (extra) =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "patch_contract_metadata_extra",
+        args: { extra },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "patch_contract_metadata_extra",
+        args: { extra },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+    try {
+      await patchExtra("{not json");
+      assert(false);
+    } catch (e) {
+      assert(/extra must be a valid JSON string/.test(e.toString()));
+    }
+
+    const res = await patchExtra(extra);
+    const event = res.receipts_outcome
+      .flatMap(({ outcome }) => outcome.logs)
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .find(({ event }) => event === "contract_metadata_update");
+    assert.deepStrictEqual(event.data[0].fields, ["extra"]);
+
+    let metadata = await contractAccount.viewFunction(contractId, "nft_metadata");
+    assert.strictEqual(metadata.extra, extra);
+    assert.deepStrictEqual(JSON.parse(metadata.extra).links, { twitter: "https://twitter.com/satori" });
+    // NEP-177 fields untouched
+    assert.strictEqual(metadata.spec, "nft-1.0.0");
+
+    // cleared extra is omitted, as for contracts that never set it
+    await patchExtra(null);
+    metadata = await contractAccount.viewFunction(contractId, "nft_metadata");
+    assert(!("extra" in metadata));
+  });
+
+
+  it("should burn a batch of tokens across types", async function () {
+    const titles = ["burn-batch-a" + Date.now(), "burn-batch-b" + Date.now()];
+    const tokenIds = [];
+    for (const [idx, title] of titles.entries()) {
+      const copies = idx === 0 ? 3 : 2;
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies,
+          },
+          assets: [["1.jpg", copies.toString(), "1.json"]],
+          royalty: { [bobId]: BOB_ROYALTY },
+          cover_asset: "1.jpg",
+        },
+        parseNearAmount("1")
+      );
+      for (let i = 0; i < copies; i++) {
+        const res = await contractAccount.functionCall({
+          contractId,
+          methodName: "nft_mint_type",
+          args: { token_type_title: title, receiver_id: contractId },
+          gas,
+          attachedDeposit: parseNearAmount("0.1"),
+        });
+        tokenIds.push(JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id);
+      }
+    }
+
+    // alice neither owns nor is approved for these
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "nft_burn_batch",
+        args: { token_ids: tokenIds },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Sender not approved/.test(e.toString()));
+    }
+
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_burn_batch",
+      args: { token_ids: tokenIds },
+      gas,
+      attachedDeposit: "1",
+    });
+    const events = res.receipts_outcome
+      .flatMap(({ outcome }) => outcome.logs)
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .filter(({ event }) => event === "nft_burn");
+    assert.strictEqual(events.length, 1);
+    assert.deepStrictEqual(events[0].data, [{ owner_id: contractId, token_ids: tokenIds }]);
+
+    for (const [idx, title] of titles.entries()) {
+      const supply = await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title });
+      assert.strictEqual(supply, "0");
+      const burned = await contractAccount.viewFunction(contractId, "nft_burned_supply_for_type", { token_type_title: title });
+      assert.strictEqual(burned, idx === 0 ? "3" : "2");
+    }
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_token", { token_id: tokenIds[0] }), null);
+  });
+
+
+  // NB: legacy (pre-generative) NFTs can't be minted on a fresh deployment, as every type now has assets, so only the
+  // non-legacy case is covered here
+  it("should report NFTs minted from assets as non-legacy", async function () {
+    const title = "legacy-check" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 1,
+        },
+        assets: [["1.jpg", "1", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: { token_type_title: title, receiver_id: contractId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+
+    const raw = await contractAccount.viewFunction(contractId, "nft_token_raw_metadata", { token_id });
+    assert.strictEqual(raw.media, "1.jpg");
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_is_legacy_token", { token_id }), false);
+
+    try {
+      await contractAccount.viewFunction(contractId, "nft_is_legacy_token", { token_id: "999999:1" });
+      assert(false);
+    } catch (e) {
+      assert(/Token not found/.test(e.toString()));
+    }
+  });
+
+
+  it("should require cover_asset to be one of the type's assets", async function () {
+    const title = "cover-asset-check" + Date.now();
+    const typeArgs = (cover_asset) => ({
+      metadata: {
+        title,
+        media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+        copies: 2,
+      },
+      assets: [
+        ["1.jpg", "1", "1.json"],
+        ["2.jpg", "1", "2.json"],
+      ],
+      royalty: { [bobId]: BOB_ROYALTY },
+      cover_asset,
+    });
+
+    try {
+      await testUtils.createType(contractAccount, contractId, typeArgs("1.jgp"), parseNearAmount("1"));
+      assert(false);
+    } catch (e) {
+      assert(/cover_asset '1.jgp' must be the filename of one of the type's assets/.test(e.toString()));
+    }
+
+    await testUtils.createType(contractAccount, contractId, typeArgs("2.jpg"), parseNearAmount("1"));
+    const token_type = await contractAccount.viewFunction(contractId, "nft_get_type", { token_type_title: title });
+    assert.strictEqual(token_type.cover_asset, "2.jpg");
+  });
+
+
+  it("should estimate the storage cost of creating a type", async function () {
+    const title = "estimate-cost" + Date.now();
+    const metadata = {
+      title,
+      media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+      copies: 30,
+    };
+    const assets = [
+      ["1.jpg", "10", "1.json"],
+      ["2.jpg", "10", "2.json"],
+      ["3.jpg", "10", "3.json", "a".repeat(64)],
+    ];
+    const estimate = await contractAccount.viewFunction(contractId, "estimate_create_type_cost", { metadata, assets });
+
+    const usageBefore = await contractAccount.viewFunction(contractId, "contract_storage_usage");
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata,
+        assets,
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const usageAfter = await contractAccount.viewFunction(contractId, "contract_storage_usage");
+
+    // deposit kept by nft_create_type is the storage it used; the estimate assumes `default_royalty`, so allow a few bytes
+    const STORAGE_BYTE_COST = new BN("10000000000000000000");
+    const actual = STORAGE_BYTE_COST.mul(new BN(usageAfter).sub(new BN(usageBefore)));
+    const diffBytes = new BN(estimate).sub(actual).abs().div(STORAGE_BYTE_COST);
+    assert(diffBytes.lte(new BN(64)), `estimate ${estimate} vs actual ${actual.toString()}`);
+  });
+
+
+  it("should reject a batch mint exceeding remaining supply upfront", async function () {
+    const title = "batch-supply" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 3,
+        },
+        assets: [["1.jpg", "3", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const batchMint = (count) =>
+      contractAccount.functionCall({
+        contractId,
+        methodName: "nft_batch_mint_type",
+        args: {
+          token_type_title: title,
+          receiver_ids: Array(count).fill(contractId),
+        },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+
+    await batchMint(1);
+    try {
+      await batchMint(3);
+      assert(false);
+    } catch (e) {
+      assert(/batch exceeds remaining supply: requested 3, available 2/.test(e.toString()));
+    }
+    // nothing minted by the rejected batch
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title }), "1");
+
+    // exactly the remaining supply is fine
+    await batchMint(2);
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_supply_for_type", { token_type_title: title }), "3");
+  });
+
+
+  it("should bump updated_at of NFTs when their media is patched", async function () {
+    const title = "updated-at" + Date.now();
+    const assets = [["old.jpg", "2", ""]];
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 2,
+        },
+        assets,
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: assets[0][0],
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: { token_type_title: title, receiver_id: contractId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id, metadata: mintedMetadata } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    // set on mint (block timestamp in nanoseconds, as a string)
+    assert(new BN(mintedMetadata.updated_at).gtn(0));
+    const raw = await contractAccount.viewFunction(contractId, "nft_token_raw_metadata", { token_id });
+    assert.strictEqual(raw.updated_at, mintedMetadata.updated_at);
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "patch_media_and_assets_for_token_type",
+      args: {
+        token_type_title: title,
+        media: "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq",
+        assets: [["new.jpg", "1", ""]],
+      },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+
+    const { metadata } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    assert.strictEqual(metadata.media, "bafybeiemk2l5uo3q4ebgrl5hyfjf2yytz7bvbt6pskj7bmhtszs3r6ttaq/new.jpg");
+    assert(new BN(metadata.updated_at).gt(new BN(mintedMetadata.updated_at)));
+  });
+
+
+  it("should filter nft_get_types by enabled & revealed state", async function () {
+    const suffix = Date.now();
+    const [live, disabled, unrevealed] = ["live", "disabled", "unrevealed"].map((state) => `types-filter-${state}-${suffix}`);
+    for (const title of [live, disabled, unrevealed]) {
+      await testUtils.createType(
+        contractAccount,
+        contractId,
+        {
+          metadata: {
+            title,
+            media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+            copies: 1,
+          },
+          assets: [["1.jpg", "1", "1.json"]],
+          royalty: { [bobId]: BOB_ROYALTY },
+          cover_asset: "1.jpg",
+        },
+        parseNearAmount("1")
+      );
+    }
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_set_type_enabled",
+      args: { token_type_title: disabled, enabled: false },
+      gas,
+    });
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_set_type_revealed",
+      args: { token_type_title: unrevealed, revealed: false },
+      gas,
+    });
+
+    // pages through the filtered list
+    const getTitles = async (filters) => {
+      const titles = [];
+      for (let from_index = 0; ; from_index += 50) {
+        const page = await contractAccount.viewFunction(contractId, "nft_get_types", {
+          from_index: from_index.toString(),
+          limit: 50,
+          ...filters,
+        });
+        titles.push(...page.map(({ metadata }) => metadata.title));
+        if (page.length < 50) return titles.filter((title) => title.endsWith(`-${suffix}`));
+      }
+    };
+
+    assert.deepStrictEqual(await getTitles({}), [live, disabled, unrevealed]);
+    assert.deepStrictEqual(await getTitles({ only_enabled: true }), [live, unrevealed]);
+    assert.deepStrictEqual(await getTitles({ only_revealed: true }), [live, disabled]);
+    assert.deepStrictEqual(await getTitles({ only_enabled: true, only_revealed: true }), [live]);
+  });
+
+
+  it("should allow the contract owner to clear approvals on a token they don't own", async function () {
+    const title = "admin-revoke" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 1,
+        },
+        assets: [["1.jpg", "1", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: { token_type_title: title, receiver_id: aliceId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_approve",
+      args: { token_id, account_id: marketId },
+      gas,
+      attachedDeposit: parseNearAmount("0.01"),
+    });
+    assert.deepStrictEqual(await contractAccount.viewFunction(contractId, "nft_token_approvals", { token_id }), { [marketId]: 1 });
+
+    // only the contract owner
+    try {
+      await bob.functionCall({
+        contractId,
+        methodName: "nft_admin_revoke_all",
+        args: { token_id },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_admin_revoke_all",
+      args: { token_id },
+      gas,
+      attachedDeposit: "1",
+    });
+    assert.deepStrictEqual(await contractAccount.viewFunction(contractId, "nft_token_approvals", { token_id }), {});
+    const { owner_id } = await contractAccount.viewFunction(contractId, "nft_token", { token_id });
+    assert.strictEqual(owner_id, aliceId);
+  });
+
+
+  it("should count NFTs minted to each account, regardless of later transfers", async function () {
+    const title = "minted-count" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 3,
+        },
+        assets: [["1.jpg", "3", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const tokenIds = [];
+    for (const receiver_id of [aliceId, aliceId, bobId]) {
+      const res = await contractAccount.functionCall({
+        contractId,
+        methodName: "nft_mint_type",
+        args: { token_type_title: title, receiver_id },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      tokenIds.push(JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString()).token_id);
+    }
+    const mintedCount = (account_id) =>
+      contractAccount.viewFunction(contractId, "nft_minted_count", { token_type_title: title, account_id });
+
+    // alice transfers one of hers to bob
+    await alice.functionCall({
+      contractId,
+      methodName: "nft_transfer",
+      args: { receiver_id: bobId, token_id: tokenIds[0] },
+      gas,
+      attachedDeposit: "1",
+    });
+
+    assert.strictEqual(await mintedCount(aliceId), "2");
+    assert.strictEqual(await mintedCount(bobId), "1");
+    assert.strictEqual(await mintedCount(marketId), "0");
+    const bobTokens = await contractAccount.viewFunction(contractId, "nft_tokens_for_owner_by_types", {
+      account_id: bobId,
+      token_type_titles: [title],
+    });
+    assert.strictEqual(bobTokens.length, 2);
+  });
+
+
+  it("should bump contract version & commit sha together", async function () {
+    const before = await contractAccount.viewFunction(contractId, "contract_source_metadata");
+    const version = "v" + Date.now();
+    const commit_sha = "0123456789abcdef0123456789abcdef01234567";
+
+    try {
+      await alice.functionCall({
+        contractId,
+        methodName: "bump_version",
+        args: { version, commit_sha },
+        gas,
+        attachedDeposit: parseNearAmount("0.1"),
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Unauthorized/.test(e.toString()));
+    }
+
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "bump_version",
+      args: { version, commit_sha },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const event = res.receipts_outcome
+      .flatMap(({ outcome }) => outcome.logs)
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .find(({ event }) => event === "contract_version_update");
+    assert.deepStrictEqual(event.data[0], {
+      old_version: before.version,
+      new_version: version,
+      old_commit_sha: before.commit_sha,
+      new_commit_sha: commit_sha,
+    });
+
+    const after = await contractAccount.viewFunction(contractId, "contract_source_metadata");
+    assert.strictEqual(after.version, version);
+    assert.strictEqual(after.commit_sha, commit_sha);
+    assert.strictEqual(after.link, before.link);
+    const [, infoVersion, infoCommitSha] = await contractAccount.viewFunction(contractId, "nft_version_info");
+    assert.strictEqual(infoVersion, version);
+    assert.strictEqual(infoCommitSha, commit_sha);
+  });
+
+
+  it("should NOT allow already-migrated state to be migrated again", async function () {
+    try {
+      await contractAccount.functionCall({
+        contractId,
+        methodName: "migrate",
+        args: {},
+        gas,
+      });
+      assert(false);
+    } catch (e) {
+      assert(/deserialize|no v2.1.0 state/i.test(e.toString()));
+    }
+    // contract still works
+    const metadata = await contractAccount.viewFunction(contractId, "nft_metadata");
+    assert.strictEqual(metadata.spec, "nft-1.0.0");
+  });
+
+
+  it("should allow a token owner to burn their own token", async function () {
+    const title = "burn-own" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 1,
+        },
+        assets: [["1.jpg", "1", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const mintRes = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_mint_type",
+      args: { token_type_title: title, receiver_id: aliceId },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const { token_id } = JSON.parse(Buffer.from(mintRes.status.SuccessValue, "base64").toString());
+
+    try {
+      await bob.functionCall({
+        contractId,
+        methodName: "nft_burn",
+        args: { token_id },
+        gas,
+        attachedDeposit: "1",
+      });
+      assert(false);
+    } catch (e) {
+      assert(/Sender not approved/.test(e.toString()));
+    }
+
+    const aliceBalanceBefore = (await getAccountBalance(aliceId)).total;
+    const res = await alice.functionCall({
+      contractId,
+      methodName: "nft_burn",
+      args: { token_id },
+      gas,
+      attachedDeposit: "1",
+    });
+    // token storage was paid by the contract owner on mint, so it is refunded to them rather than the burner
+    const aliceBalanceAfter = (await getAccountBalance(aliceId)).total;
+    assert(new BN(aliceBalanceAfter).lt(new BN(aliceBalanceBefore)));
+    const event = res.receipts_outcome
+      .flatMap(({ outcome }) => outcome.logs)
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .find(({ event }) => event === "nft_burn");
+    assert.deepStrictEqual(event.data, [{ owner_id: aliceId, token_ids: [token_id] }]);
+    assert.strictEqual(await contractAccount.viewFunction(contractId, "nft_token", { token_id }), null);
+    const burned = await contractAccount.viewFunction(contractId, "nft_burned_supply_for_type", { token_type_title: title });
+    assert.strictEqual(burned, "1");
+  });
+
+
+  it("should log a single nft_mint event for a batch mint", async function () {
+    const title = "batch-mint-event" + Date.now();
+    await testUtils.createType(
+      contractAccount,
+      contractId,
+      {
+        metadata: {
+          title,
+          media: "bafkreibael4nenayqy45ijuvgcpkmyscbt3q35mtbzbeabopmugdwr5r64",
+          copies: 3,
+        },
+        assets: [["1.jpg", "3", "1.json"]],
+        royalty: { [bobId]: BOB_ROYALTY },
+        cover_asset: "1.jpg",
+      },
+      parseNearAmount("1")
+    );
+    const res = await contractAccount.functionCall({
+      contractId,
+      methodName: "nft_batch_mint_type",
+      args: { token_type_title: title, receiver_ids: [aliceId, bobId, aliceId] },
+      gas,
+      attachedDeposit: parseNearAmount("0.1"),
+    });
+    const tokens = JSON.parse(Buffer.from(res.status.SuccessValue, "base64").toString());
+    const events = res.receipts_outcome
+      .flatMap(({ outcome }) => outcome.logs)
+      .filter((log) => log.startsWith("EVENT_JSON:"))
+      .map((log) => JSON.parse(log.slice("EVENT_JSON:".length)))
+      .filter(({ event }) => event === "nft_mint");
+    assert.strictEqual(events.length, 1);
+    assert.deepStrictEqual(events[0].data, [
+      { owner_id: aliceId, token_ids: [tokens[0].token_id, tokens[2].token_id] },
+      { owner_id: bobId, token_ids: [tokens[1].token_id] },
+    ]);
+  });
 });